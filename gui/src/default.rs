@@ -8,18 +8,37 @@
 //! on [TextField]), these widgets must also be parameterized by the `Style` of those dependees.
 //!
 mod button;
+mod checkbox;
 mod container;
+mod image;
+mod radio;
+mod scroll;
 mod select;
+mod slider;
 mod text;
+mod text_input;
 
 pub use button::*;
+pub use checkbox::*;
 pub use container::*;
+pub use image::*;
+pub use radio::*;
+pub use scroll::*;
 pub use select::*;
+pub use slider::*;
 pub use text::*;
+pub use text_input::*;
 
 /// Default height of buttons (this is always configurable at runtime through WidgetConfig of each
 /// widget)
 pub const DEFAULT_BUTTON_HEIGHT: f32 = 30.0;
+/// Default size of the box of checkboxes (this is always configurable at runtime through
+/// WidgetConfig of the box)
+pub const DEFAULT_CHECKBOX_SIZE: f32 = 16.0;
+/// Default length and thickness of sliders (this is always configurable at runtime through
+/// WidgetConfig of each slider and its thumb)
+pub const DEFAULT_SLIDER_LENGTH: f32 = 150.0;
+pub const DEFAULT_SLIDER_THICKNESS: f32 = 20.0;
 
 /// Really just a shortcut used internally (has to be `pub`). Ignore.
 pub trait StyleBound: Default + Send + Sync + Clone + std::fmt::Debug + 'static {}
@@ -34,3 +53,16 @@ impl SelectStyle for () {
     type TextField = ();
     type Button = ();
 }
+impl CheckboxStyle for () {
+    type TextField = ();
+}
+impl RadioStyle for () {
+    type TextField = ();
+    type Button = ();
+}
+impl ScrollAreaStyle for () {}
+impl ImageStyle for () {}
+impl SliderStyle for () {}
+impl TextInputStyle for () {
+    type TextField = ();
+}
@@ -1,6 +1,7 @@
 use super::*;
 use crate::*;
 use interactive::*;
+use winit::event::MouseButton;
 
 pub trait ButtonStyle: StyleBound {
     /// Style of contained text field
@@ -37,6 +38,9 @@ impl<Style: ButtonStyle> Interactive for Button<Style> {
             keyboard: false,
         }
     }
+    fn focusable(&self) -> bool {
+        true
+    }
 }
 
 #[derive(LensInternal, Debug)]
@@ -67,7 +71,10 @@ impl<Style: ButtonStyle> Interactive for ToggleButton<Style> {
     fn update(&mut self, id: Id, local_events: Vec<Event>, ctx: &mut WidgetContext) {
         for event in local_events {
             if id == event.id {
-                if let EventKind::Press = event.kind {
+                if let EventKind::Press {
+                    button: MouseButton::Left,
+                } = event.kind
+                {
                     self.state = !self.state;
                     ctx.gui
                         .borrow_mut()
@@ -82,6 +89,9 @@ impl<Style: ButtonStyle> Interactive for ToggleButton<Style> {
             keyboard: false,
         }
     }
+    fn focusable(&self) -> bool {
+        true
+    }
 }
 
 // -------
@@ -100,6 +110,7 @@ impl<Style> ToggleButton<Style> {
 mod test {
     use crate::test_common::*;
     use crate::*;
+    use winit::event::MouseButton;
     #[test]
     fn test_toggle_button_state() {
         let mut fix = TestFixture::fixture();
@@ -107,7 +118,12 @@ mod test {
 
         let ((_, _), (events, _)) = fix.click_widget("ToggleButton 0");
 
-        assert_events!(events, vec![EventKind::Release]);
+        assert_events!(
+            events,
+            vec![EventKind::Release {
+                button: MouseButton::Left
+            }]
+        );
 
         let btn = fix.gui.get("ToggleButton 0");
         let btn = btn.downcast_ref::<ToggleButton>().unwrap();
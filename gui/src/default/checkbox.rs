@@ -0,0 +1,305 @@
+use super::*;
+use crate::*;
+use interactive::*;
+use winit::event::{MouseButton, VirtualKeyCode};
+
+pub trait CheckboxStyle: StyleBound {
+    /// Style of the label
+    type TextField: TextFieldStyle;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheckState {
+    Unchecked,
+    Checked,
+    /// Neither checked nor unchecked, typically because some but not all of the checkboxes that
+    /// this one represents are checked (see `CheckboxGroup`)
+    Indeterminate,
+}
+impl Default for CheckState {
+    fn default() -> Self {
+        CheckState::Unchecked
+    }
+}
+impl CheckState {
+    /// The state after the user clicks the checkbox
+    pub fn toggled(self) -> CheckState {
+        match self {
+            CheckState::Checked => CheckState::Unchecked,
+            CheckState::Unchecked | CheckState::Indeterminate => CheckState::Checked,
+        }
+    }
+}
+
+/// A box that can be checked, with a label to the right of it.
+/// The box is an empty `Container` for drawers to draw on (`Checkbox::check_box`), and the
+/// label is a `TextField` (`Checkbox::label`).
+/// Pressing the checkbox or pressing Space while it has focus toggles it.
+#[derive(LensInternal, Debug)]
+pub struct Checkbox<Style> {
+    #[lens]
+    pub state: crate::default::CheckState,
+    pub style: Style,
+
+    label: String,
+    box_id: Id,
+    label_id: Id,
+}
+impl<Style: CheckboxStyle> Checkbox<Style> {
+    pub fn new() -> Checkbox<Style> {
+        Checkbox {
+            state: CheckState::Unchecked,
+            style: Style::default(),
+            label: String::new(),
+            box_id: 0,
+            label_id: 0,
+        }
+    }
+    /// Initial text of the label
+    pub fn with_label(mut self, label: String) -> Self {
+        self.label = label;
+        self
+    }
+    pub fn box_id(&self) -> Id {
+        self.box_id
+    }
+    pub fn label_id(&self) -> Id {
+        self.label_id
+    }
+}
+impl<Style: CheckboxStyle> Interactive for Checkbox<Style> {
+    fn init(&mut self, ctx: &mut WidgetContext) -> WidgetConfig {
+        self.box_id = ctx.insert_child(Container::new());
+        ctx.access_child(self.box_id).configure(|config| {
+            config.set_size(DEFAULT_CHECKBOX_SIZE, DEFAULT_CHECKBOX_SIZE);
+        });
+        self.label_id = ctx.insert_child(TextField::<Style::TextField>::new(self.label.clone()));
+        WidgetConfig::default()
+            .size_hint(SizeHint::Minimize, SizeHint::Minimize)
+            .layout(Axis::X, false, Anchor::Center, 6.0)
+    }
+    fn update(&mut self, id: Id, local_events: Vec<Event>, ctx: &mut WidgetContext) {
+        for event in local_events {
+            if event.id != id {
+                continue;
+            }
+            let toggle = match event.kind {
+                EventKind::Press {
+                    button: MouseButton::Left,
+                } => true,
+                EventKind::KeyPress {
+                    key: VirtualKeyCode::Space,
+                    ..
+                } => true,
+                _ => false,
+            };
+            if toggle {
+                self.state = self.state.toggled();
+                ctx.push_event(EventKind::change(Self::state));
+            }
+        }
+    }
+    fn captures(&self) -> Capture {
+        Capture {
+            mouse: true,
+            keyboard: false,
+        }
+    }
+    fn focusable(&self) -> bool {
+        true
+    }
+}
+
+/// A column of checkboxes, where the first one ("select all") reflects and controls the rest.
+///
+/// The first checkbox is `Checked` or `Unchecked` if all the others are, and `Indeterminate`
+/// otherwise. Toggling it checks or unchecks all the others.
+/// Insert the other checkboxes as children of the group, e.g. with `Widget::insert_child`.
+#[derive(Debug)]
+pub struct CheckboxGroup<Style> {
+    pub style: Style,
+
+    label: String,
+    all_id: Id,
+}
+impl<Style: CheckboxStyle> CheckboxGroup<Style> {
+    pub fn new() -> CheckboxGroup<Style> {
+        CheckboxGroup {
+            style: Style::default(),
+            label: String::new(),
+            all_id: 0,
+        }
+    }
+    /// Initial text of the label of the "select all" checkbox
+    pub fn with_label(mut self, label: String) -> Self {
+        self.label = label;
+        self
+    }
+    /// Id of the "select all" checkbox
+    pub fn all_id(&self) -> Id {
+        self.all_id
+    }
+}
+impl<Style: CheckboxStyle> Interactive for CheckboxGroup<Style> {
+    fn init(&mut self, ctx: &mut WidgetContext) -> WidgetConfig {
+        self.all_id = ctx.insert_child(Checkbox::<Style>::new().with_label(self.label.clone()));
+        WidgetConfig::default().layout(Axis::Y, false, Anchor::Min, 2.0)
+    }
+    fn update(&mut self, _id: Id, local_events: Vec<Event>, ctx: &mut WidgetContext) {
+        let members = ctx
+            .values()
+            .filter(|w| w.get_id() != self.all_id && w.downcast_ref::<Checkbox<Style>>().is_some())
+            .map(|w| w.get_id())
+            .collect::<Vec<_>>();
+
+        // "select all" toggled by the user
+        let all_toggled = local_events
+            .iter()
+            .any(|event| event.id == self.all_id && event.kind.is_change(Checkbox::<Style>::state));
+        if all_toggled {
+            let state = *ctx
+                .access_child(self.all_id)
+                .chain(Checkbox::<Style>::state)
+                .get();
+            for id in members.iter() {
+                ctx.access_child(*id)
+                    .chain(Checkbox::<Style>::state)
+                    .put(state);
+            }
+        }
+
+        // Reflect the state of the members
+        if !members.is_empty() {
+            let states = members
+                .iter()
+                .map(|id| *ctx.access_child(*id).chain(Checkbox::<Style>::state).get())
+                .collect::<Vec<_>>();
+            let state = if states.iter().all(|s| *s == CheckState::Checked) {
+                CheckState::Checked
+            } else if states.iter().all(|s| *s == CheckState::Unchecked) {
+                CheckState::Unchecked
+            } else {
+                CheckState::Indeterminate
+            };
+            ctx.access_child(self.all_id)
+                .chain(Checkbox::<Style>::state)
+                .put(state);
+        }
+    }
+}
+
+// -------
+// Lenses
+// -------
+
+use crate::widget::lenses::FirstChildLens;
+#[allow(non_upper_case_globals)]
+impl<Style> Checkbox<Style> {
+    pub const check_box: FirstChildLens = FirstChildLens;
+    pub const label: LabelLens<Style> = LabelLens {
+        _marker: std::marker::PhantomData,
+    };
+}
+#[allow(non_upper_case_globals)]
+impl<Style> CheckboxGroup<Style> {
+    pub const all: FirstChildLens = FirstChildLens;
+}
+
+#[derive(Clone)]
+pub struct LabelLens<Style> {
+    _marker: std::marker::PhantomData<Style>,
+}
+impl<Style: CheckboxStyle> Lens for LabelLens<Style> {
+    type Source = Widget;
+    type Target = Widget;
+    fn get<'a>(&self, w: &'a Widget) -> &'a Widget {
+        let id = w.downcast_ref::<Checkbox<Style>>().unwrap().label_id();
+        &w.children()[&id]
+    }
+    fn get_mut<'a>(&self, w: &'a mut Widget) -> &'a mut Widget {
+        let id = w.downcast_mut::<Checkbox<Style>>().unwrap().label_id();
+        w.get_child_mut(id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::default::CheckState;
+    use crate::test_common::*;
+    use crate::{lens::*, *};
+    use winit::event::VirtualKeyCode;
+    #[test]
+    fn test_checkbox() {
+        let mut gui = TestGui::new();
+        let id = gui.insert_in_root(Checkbox::new().with_label("Sound".to_string()));
+        gui.update();
+        gui.update();
+        assert_eq!(
+            gui.access(id)
+                .chain(Checkbox::label)
+                .chain(TextField::text)
+                .get(),
+            "Sound"
+        );
+
+        let pos = gui.get(id).pos + gui.get(id).size / 2.0;
+        let (events, _) = gui.press(pos);
+        gui.release();
+        assert!(events.contains(&Event::change(id, Checkbox::state)));
+        assert_eq!(
+            *gui.access(id).chain(Checkbox::state).get(),
+            CheckState::Checked
+        );
+
+        // Space toggles the focused checkbox
+        gui.press_key(VirtualKeyCode::Space);
+        assert_eq!(
+            *gui.access(id).chain(Checkbox::state).get(),
+            CheckState::Unchecked
+        );
+    }
+    #[test]
+    fn test_checkbox_group() {
+        let mut gui = TestGui::new();
+        let group = gui.insert_in_root(CheckboxGroup::new().with_label("All".to_string()));
+        let members = (0..3)
+            .map(|_| gui.get_mut(group).insert_child(Checkbox::new()))
+            .collect::<Vec<_>>();
+        let all = gui
+            .get(group)
+            .downcast_ref::<CheckboxGroup>()
+            .unwrap()
+            .all_id();
+        gui.update();
+        gui.update();
+        let state = |gui: &mut TestGui, id: Id| *gui.access(id).chain(Checkbox::state).get();
+        let click = |gui: &mut TestGui, id: Id| {
+            let pos = gui.get(id).pos + gui.get(id).size / 2.0;
+            let events = gui.press(pos).0;
+            gui.release();
+            events
+        };
+
+        // The group reflects its members
+        click(&mut gui, members[0]);
+        assert_eq!(state(&mut gui, all), CheckState::Indeterminate);
+        click(&mut gui, members[1]);
+        click(&mut gui, members[2]);
+        assert_eq!(state(&mut gui, all), CheckState::Checked);
+        click(&mut gui, members[2]);
+        assert_eq!(state(&mut gui, all), CheckState::Indeterminate);
+
+        // ... and controls them
+        let events = click(&mut gui, all);
+        assert_eq!(state(&mut gui, all), CheckState::Checked);
+        for member in members.iter() {
+            assert_eq!(state(&mut gui, *member), CheckState::Checked);
+        }
+        assert!(events.contains(&Event::change(members[2], Checkbox::state)));
+        click(&mut gui, all);
+        for member in members.iter() {
+            assert_eq!(state(&mut gui, *member), CheckState::Unchecked);
+        }
+        assert_eq!(state(&mut gui, all), CheckState::Unchecked);
+    }
+}
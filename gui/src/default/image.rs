@@ -0,0 +1,48 @@
+use super::*;
+use crate::*;
+use interactive::*;
+
+pub trait ImageStyle: StyleBound {}
+
+/// Shows an image, stretched over the widget.
+/// `gui` does not load images: `source` is a key (such as a file path) that the drawer resolves.
+/// By default the widget takes the size `intrinsic_size`, which typically is the size of the
+/// image (see `with_size`).
+#[derive(LensInternal, Debug)]
+pub struct Image<Style> {
+    #[lens]
+    pub source: String,
+    #[lens]
+    pub intrinsic_size: crate::Vec2,
+    pub style: Style,
+}
+impl<Style: ImageStyle> Image<Style> {
+    pub fn new(source: String) -> Image<Style> {
+        Image {
+            source,
+            intrinsic_size: Vec2::zero(),
+            style: Style::default(),
+        }
+    }
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.intrinsic_size = Vec2::new(width, height);
+        self
+    }
+}
+impl<Style: ImageStyle> Interactive for Image<Style> {
+    fn init(&mut self, _ctx: &mut WidgetContext) -> WidgetConfig {
+        WidgetConfig::default().size_hint(SizeHint::Intrinsic, SizeHint::Intrinsic)
+    }
+    fn image(&self) -> Option<&str> {
+        Some(&self.source)
+    }
+    fn set_intrinsic_size(&mut self, size: Vec2, ctx: &mut WidgetContext) {
+        if size != self.intrinsic_size {
+            self.intrinsic_size = size;
+            ctx.push_event(EventKind::change(Self::intrinsic_size));
+        }
+    }
+    fn determine_size(&self, _drawer: &mut dyn TextCalculator) -> Option<Vec2> {
+        Some(self.intrinsic_size)
+    }
+}
@@ -1,54 +1,91 @@
-
+use super::*;
 use crate::*;
+use indexmap::IndexMap;
+use winit::event::MouseButton;
 
-#[derive(Debug, Clone)]
-pub struct Radio {
-    pub text: String,
+pub trait RadioStyle: StyleBound {
+    type TextField: TextFieldStyle;
+    type Button: ButtonStyle;
 }
-impl Radio {
-    pub fn new(text: String) -> Radio {
-        Radio { text }
-    }
+
+#[derive(Debug, Clone, PartialEq)]
+struct RadioOption {
+    pub name: String,
+    pub value: String,
 }
-impl Interactive for Radio {
-    fn handle_event(&mut self, _: WidgetEvent) -> bool {
-        false
-    }
-    fn captures(&self) -> Capture {
-        Capture {
-            mouse: false,
-            keyboard: false,
+
+/// A list of mutually exclusive options, each shown as a `ToggleButton`.
+/// Exactly the button of the chosen option (if any) is toggled on.
+#[derive(LensInternal, Debug)]
+pub struct RadioGroup<Style> {
+    // configuration
+    options: Vec<RadioOption>,
+    pub style: Style,
+
+    // runtime state
+    /// Value of the chosen option. `None` until an option is chosen.
+    #[lens]
+    value: Option<String>,
+    /// map from ID of button to option index
+    opt_map: IndexMap<Id, usize>,
+}
+impl<Style: RadioStyle> RadioGroup<Style> {
+    pub fn new() -> RadioGroup<Style> {
+        RadioGroup {
+            options: Vec::new(),
+            value: None,
+            opt_map: IndexMap::new(),
+            style: Style::default(),
         }
     }
-    fn children(&mut self) -> Vec<&mut WidgetInternal> {
-        vec![] // TODO radio buttons
+    /// Add an option. Options have to be added before the widget is inserted into `Gui`.
+    pub fn with_option(mut self, name: String, value: String) -> Self {
+        self.options.push(RadioOption { name, value });
+        self
+    }
+    pub fn get_widget_for_option(&self, value: &str) -> Option<Id> {
+        self.opt_map
+            .iter()
+            .find(|(_, opt_idx)| self.options[**opt_idx].value == value)
+            .map(|(id, _)| *id)
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct RadioButton {
-    pub text: String,
-    pub state: bool,
-}
-impl RadioButton {
-    pub fn new(text: String) -> RadioButton {
-        RadioButton {
-            text,
-            state: false,
+impl<Style: RadioStyle> Interactive for RadioGroup<Style> {
+    fn init(&mut self, ctx: &mut WidgetContext) -> WidgetConfig {
+        for (i, option) in self.options.iter().enumerate() {
+            let id = ctx.insert_child(ToggleButton::<Style::Button>::new());
+            ctx.access_child(id)
+                .chain(ToggleButton::<Style::Button>::text_field)
+                .chain(TextField::<Style::TextField>::text)
+                .put(option.name.clone());
+            self.opt_map.insert(id, i);
         }
+        WidgetConfig::default().layout(Axis::Y, false, Anchor::Min, 2.0)
     }
-}
-impl Interactive for RadioButton {
-    fn handle_event(&mut self, event: WidgetEvent) -> bool {
-        if let WidgetEvent::Release = event {
-            if !self.state {
-                self.state = true;
-                true
-            } else {
-                false
+    fn update(&mut self, _id: Id, local_events: Vec<Event>, ctx: &mut WidgetContext) {
+        for event in local_events.iter() {
+            if let Some(opt_idx) = self.opt_map.get(&event.id) {
+                if let EventKind::Press {
+                    button: MouseButton::Left,
+                } = event.kind
+                {
+                    ctx.consume(event);
+                    let value = Some(self.options[*opt_idx].value.clone());
+                    if value != self.value {
+                        self.value = value;
+                        ctx.push_event(EventKind::change(Self::value));
+                    }
+                }
             }
-        } else {
-            false
+        }
+        // Buttons toggle themselves when pressed, and `value` may have been changed through a
+        // lens, so always make sure that only the chosen option is on.
+        for (id, opt_idx) in self.opt_map.iter() {
+            let on = self.value.as_ref() == Some(&self.options[*opt_idx].value);
+            ctx.access_child(*id)
+                .chain(ToggleButton::<Style::Button>::state)
+                .put(on);
         }
     }
     fn captures(&self) -> Capture {
@@ -57,7 +94,73 @@ impl Interactive for RadioButton {
             keyboard: false,
         }
     }
-    fn children(&mut self) -> Vec<&mut WidgetInternal> {
-        vec![] // TODO text field
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_common::*;
+    use crate::{lens::*, *};
+    #[test]
+    fn test_radio_group() {
+        let mut gui = TestGui::new();
+        let id = gui.insert_in_root(
+            RadioGroup::new()
+                .with_option("Easy".to_string(), "easy".to_string())
+                .with_option("Hard".to_string(), "hard".to_string()),
+        );
+        gui.update();
+        gui.update();
+        let radio = gui.get(id).downcast_ref::<RadioGroup>().unwrap();
+        let (easy, hard) = (
+            radio.get_widget_for_option("easy").unwrap(),
+            radio.get_widget_for_option("hard").unwrap(),
+        );
+        let state = |gui: &mut TestGui, button: Id| {
+            *gui.access(id)
+                .chain(Widget::child(button))
+                .chain(ToggleButton::state)
+                .get()
+        };
+        let center = |gui: &TestGui, button: Id| {
+            let w = gui.get(button);
+            w.pos + w.size / 2.0
+        };
+
+        // Choose an option
+        let pos = center(&gui, hard);
+        let (events, _) = gui.press(pos);
+        gui.release();
+        assert!(events.contains(&Event::change(id, RadioGroup::value)));
+        assert_eq!(
+            *gui.access(id).chain(RadioGroup::value).get(),
+            Some("hard".to_string())
+        );
+        assert!(state(&mut gui, hard));
+        assert!(!state(&mut gui, easy));
+
+        // Pressing the chosen option again does not turn it off
+        let (events, _) = gui.press(pos);
+        gui.release();
+        assert!(!events.contains(&Event::change(id, RadioGroup::value)));
+        assert!(state(&mut gui, hard));
+
+        // Choosing another option turns the first one off
+        let pos = center(&gui, easy);
+        gui.press(pos);
+        gui.release();
+        assert_eq!(
+            *gui.access(id).chain(RadioGroup::value).get(),
+            Some("easy".to_string())
+        );
+        assert!(state(&mut gui, easy));
+        assert!(!state(&mut gui, hard));
+
+        // Changing the value through a lens updates the buttons
+        gui.access(id)
+            .chain(RadioGroup::value)
+            .put(Some("hard".to_string()));
+        gui.update();
+        assert!(state(&mut gui, hard));
+        assert!(!state(&mut gui, easy));
     }
 }
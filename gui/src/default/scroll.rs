@@ -0,0 +1,229 @@
+use super::*;
+use crate::*;
+use interactive::*;
+use winit::event::MouseButton;
+
+pub trait ScrollAreaStyle: StyleBound {}
+
+/// Shows a part of its content, which is laid out at its natural size and can be scrolled with
+/// the mouse wheel or by dragging.
+///
+/// The content is a `Container` (the only child) which can be accessed with
+/// `ScrollArea::content`; insert widgets into that one.
+/// By default, the scroll area fills its parent (see `SizeHint::Flex`), but its size can be
+/// configured as with any other widget.
+#[derive(LensInternal, Debug)]
+pub struct ScrollArea<Style> {
+    /// How far the content is scrolled. Zero means that the top left corner of the content is
+    /// visible.
+    #[lens]
+    pub scroll: crate::Vec2,
+    /// Distance scrolled per step of the mouse wheel
+    pub scroll_speed: f32,
+    pub style: Style,
+}
+impl<Style: ScrollAreaStyle> ScrollArea<Style> {
+    pub fn new() -> ScrollArea<Style> {
+        ScrollArea {
+            scroll: Vec2::zero(),
+            scroll_speed: 20.0,
+            style: Style::default(),
+        }
+    }
+    /// The largest possible `scroll`, such that the content fills the scroll area
+    fn max_scroll(&self, ctx: &WidgetContext) -> Vec2 {
+        let content_size = ctx.values().next().map(|w| w.size).unwrap_or_default();
+        let max = content_size - ctx.size;
+        Vec2::new(max.x.max(0.0), max.y.max(0.0))
+    }
+}
+impl<Style: ScrollAreaStyle> Interactive for ScrollArea<Style> {
+    fn init(&mut self, ctx: &mut WidgetContext) -> WidgetConfig {
+        ctx.insert_child(Container::new());
+        WidgetConfig::default()
+            .size_hint(SizeHint::Flex(1.0), SizeHint::Flex(1.0))
+            .clip_children(true)
+    }
+    fn update(&mut self, id: Id, local_events: Vec<Event>, ctx: &mut WidgetContext) {
+        let max_scroll = self.max_scroll(ctx);
+        let mut scroll = self.scroll;
+        for event in local_events {
+            if event.id != id {
+                continue;
+            }
+            match event.kind {
+                EventKind::Press {
+                    button: MouseButton::Left,
+                } => ctx.gui.borrow_mut().capture_pointer(id),
+                EventKind::Drag { delta } => scroll -= delta,
+                EventKind::Scroll { delta } => {
+                    // Let an enclosing scroll area have the wheel if this one can not scroll
+                    // in that direction at all
+                    let scrollable = (delta.x != 0.0 && max_scroll.x > 0.0)
+                        || (delta.y != 0.0 && max_scroll.y > 0.0);
+                    if scrollable {
+                        scroll -= delta * self.scroll_speed;
+                        ctx.consume(&event);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        scroll = Vec2::new(
+            scroll.x.max(0.0).min(max_scroll.x),
+            scroll.y.max(0.0).min(max_scroll.y),
+        );
+        if scroll != self.scroll {
+            self.scroll = scroll;
+            ctx.push_event(EventKind::change(Self::scroll));
+        }
+    }
+    fn captures(&self) -> Capture {
+        Capture {
+            mouse: true,
+            keyboard: false,
+        }
+    }
+    fn content_offset(&self) -> Vec2 {
+        -self.scroll
+    }
+}
+
+// -------
+// Lenses
+// -------
+
+use crate::widget::lenses::FirstChildLens;
+#[allow(non_upper_case_globals)]
+impl<Style> ScrollArea<Style> {
+    pub const content: FirstChildLens = FirstChildLens;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_common::*;
+    use crate::{lens::*, *};
+    use winit::event::MouseButton;
+    #[test]
+    fn test_scroll_area() {
+        let mut gui = TestGui::new();
+        let area = gui.insert_in_root(ScrollArea::new());
+        gui.access(area).configure(|config| {
+            config.set_size(100.0, 50.0);
+        });
+        gui.access(area)
+            .chain(ScrollArea::content)
+            .configure(|config| {
+                config.set_layout_direction(Axis::Y);
+            });
+        let buttons = (0..4)
+            .map(|_| {
+                gui.access(area)
+                    .chain(ScrollArea::content)
+                    .get_widget_mut()
+                    .insert_child(Button::new())
+            })
+            .collect::<Vec<_>>();
+        gui.update();
+        gui.update();
+
+        // Buttons are 30 high, so the third button starts at y=60, outside the scroll area
+        let pos = Vec2::new(10.0, 45.0);
+        let (events, _) = gui.press(pos);
+        gui.release();
+        assert!(events.contains(&Event::new(
+            buttons[1],
+            EventKind::Press {
+                button: MouseButton::Left
+            }
+        )));
+        let (events, _) = gui.press(Vec2::new(10.0, 65.0));
+        gui.release();
+        assert!(!events.contains(&Event::new(
+            buttons[2],
+            EventKind::Press {
+                button: MouseButton::Left
+            }
+        )));
+
+        // Scroll down one step with the mouse wheel
+        let (events, _) = gui.scroll(pos, Vec2::new(0.0, -1.0));
+        assert!(events.contains(&Event::change(area, ScrollArea::scroll)));
+        assert_eq!(gui.get(buttons[0]).pos.y, -20.0);
+        assert_eq!(gui.get(buttons[2]).pos.y, 40.0);
+        let (events, _) = gui.press(pos);
+        gui.release();
+        assert!(events.contains(&Event::new(
+            buttons[2],
+            EventKind::Press {
+                button: MouseButton::Left
+            }
+        )));
+
+        // Scrolling is limited by the size of the content: 4 * 30 - 50
+        gui.scroll(pos, Vec2::new(0.0, -10.0));
+        assert_eq!(
+            *gui.access(area).chain(ScrollArea::scroll).get(),
+            Vec2::new(0.0, 70.0)
+        );
+    }
+    #[test]
+    fn test_nested_scroll() {
+        let mut gui = TestGui::new();
+        let outer = gui.insert_in_root(ScrollArea::new());
+        gui.access(outer).configure(|config| {
+            config.set_size(100.0, 60.0);
+        });
+        let inner = gui
+            .access(outer)
+            .chain(ScrollArea::content)
+            .get_widget_mut()
+            .insert_child(ScrollArea::new());
+        gui.access(inner).configure(|config| {
+            config.set_size(100.0, 30.0);
+        });
+        for area in [outer, inner].iter() {
+            gui.access(*area)
+                .chain(ScrollArea::content)
+                .configure(|config| {
+                    config.set_layout_direction(Axis::Y);
+                });
+            for _ in 0..3 {
+                gui.access(*area)
+                    .chain(ScrollArea::content)
+                    .get_widget_mut()
+                    .insert_child(Button::new());
+            }
+        }
+        gui.update();
+        gui.update();
+        let scroll = |gui: &mut TestGui, id: Id| *gui.access(id).chain(ScrollArea::scroll).get();
+
+        // The innermost scroll area under the mouse consumes the wheel
+        let (events, capture) = gui.scroll(Vec2::new(10.0, 10.0), Vec2::new(0.0, -1.0));
+        assert!(capture.mouse);
+        assert!(events.contains(&Event::change(inner, ScrollArea::scroll)));
+        assert_eq!(gui.consumed_events().len(), 1);
+        assert_eq!(scroll(&mut gui, inner), Vec2::new(0.0, 20.0));
+        assert_eq!(scroll(&mut gui, outer), Vec2::zero());
+
+        // Scrolling sideways bubbles up past both, since neither can scroll horizontally
+        let (events, _) = gui.scroll(Vec2::new(10.0, 10.0), Vec2::new(-1.0, 0.0));
+        assert!(events.contains(&Event::new(
+            outer,
+            EventKind::Scroll {
+                delta: Vec2::new(-1.0, 0.0)
+            }
+        )));
+
+        // Below the inner scroll area, the outer one scrolls
+        gui.scroll(Vec2::new(10.0, 45.0), Vec2::new(0.0, -1.0));
+        assert_eq!(scroll(&mut gui, inner), Vec2::new(0.0, 20.0));
+        assert_eq!(scroll(&mut gui, outer), Vec2::new(0.0, 20.0));
+
+        // Outside of the scroll areas, nothing consumes the wheel
+        let (_, capture) = gui.scroll(Vec2::new(200.0, 10.0), Vec2::new(0.0, -1.0));
+        assert!(!capture.mouse);
+    }
+}
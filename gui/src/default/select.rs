@@ -2,6 +2,7 @@ use super::*;
 // use crate::{*, widget::lenses::ChildLens};
 use crate::*;
 use indexmap::IndexMap;
+use winit::event::MouseButton;
 
 pub trait SelectStyle: Default + Send + Sync + Clone + std::fmt::Debug + 'static {
     type TextField: TextFieldStyle;
@@ -50,7 +51,10 @@ impl<Style: SelectStyle> Select<Style> {
     pub fn open(&mut self, ctx: &mut WidgetContext) {
         let container_id = ctx.insert_child(Container::new());
         ctx.access_child(container_id).configure(|config| {
-            config.set_placement(Placement::fixed(0.0, 0.0));
+            // Show the list right below the main button
+            config.set_placement(
+                Placement::relative(self.main_button_id, 0.0, 0.0).y_anchor(Anchor::Max),
+            );
             config.set_layout(Axis::Y, false, Anchor::Min, 2.0);
         });
         let size = self.max_size(ctx);
@@ -97,7 +101,11 @@ impl<Style: SelectStyle> Select<Style> {
         self.options
             .iter()
             .fold(None, |max: Option<Vec2>, option| {
-                let size = ctx.gui.borrow_mut().text_calc.text_size(&option.name);
+                let size = ctx
+                    .gui
+                    .borrow_mut()
+                    .text_calc(ctx.layer)
+                    .text_size(&option.name);
                 if let Some(max) = max {
                     Some(Vec2::new(max.x.max(size.x), max.y.max(size.y)))
                 } else {
@@ -145,10 +153,12 @@ impl<Style: SelectStyle> Interactive for Select<Style> {
             .access()
             .chain(ToggleButton::<Style::Button>::state)
             .get();
-        for Event { id, kind } in local_events.iter().cloned() {
+        for event in local_events.iter() {
+            let Event { id, kind } = event.clone();
             // Toggle dropdown list
             if id == self.main_button_id {
                 if kind.is_change(ToggleButton::<Style::Button>::state) {
+                    ctx.consume(event);
                     if is_open {
                         self.close(ctx);
                     } else {
@@ -159,7 +169,11 @@ impl<Style: SelectStyle> Interactive for Select<Style> {
 
             // Handle any option buttons
             if let Some(opt_idx) = self.opt_map.get(&id) {
-                if kind == EventKind::Press {
+                if let EventKind::Press {
+                    button: MouseButton::Left,
+                } = kind
+                {
+                    ctx.consume(event);
                     let opt = self.options[*opt_idx].clone();
                     let btn = ctx.get_child_mut(self.main_button_id);
                     btn.access()
@@ -0,0 +1,217 @@
+use super::*;
+use crate::*;
+use interactive::*;
+use winit::event::{MouseButton, VirtualKeyCode};
+
+pub trait SliderStyle: StyleBound {}
+
+/// Chooses a number between `min` and `max` by dragging a thumb along `axis`.
+/// The value increases to the right (`Axis::X`) or downwards (`Axis::Y`).
+///
+/// The thumb is an empty `Container` (`Slider::thumb`) for drawers to draw on.
+/// Pressing anywhere on the slider moves the thumb there, and it follows the mouse until the
+/// button is released, even outside the slider.
+/// While focused, the arrow keys move the thumb by one step, Page Up/Down by ten steps, and
+/// Home/End to either end.
+#[derive(LensInternal, Debug)]
+pub struct Slider<Style> {
+    #[lens]
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+    /// If positive, `value` is always `min` plus a multiple of `step`
+    pub step: f32,
+    pub axis: Axis,
+    pub style: Style,
+
+    thumb_id: Id,
+}
+impl<Style: SliderStyle> Slider<Style> {
+    pub fn new(min: f32, max: f32) -> Slider<Style> {
+        Slider {
+            value: min,
+            min,
+            max,
+            step: 0.0,
+            axis: Axis::X,
+            style: Style::default(),
+            thumb_id: 0,
+        }
+    }
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+    pub fn with_value(mut self, value: f32) -> Self {
+        self.value = value;
+        self
+    }
+    pub fn with_axis(mut self, axis: Axis) -> Self {
+        self.axis = axis;
+        self
+    }
+    pub fn thumb_id(&self) -> Id {
+        self.thumb_id
+    }
+    /// Clamps `value` between `min` and `max` and rounds it to the closest step
+    pub fn snap(&self, value: f32) -> f32 {
+        let value = if self.step > 0.0 {
+            self.min + ((value - self.min) / self.step).round() * self.step
+        } else {
+            value
+        };
+        value.max(self.min).min(self.max)
+    }
+    /// Distance moved by the arrow keys
+    fn key_step(&self) -> f32 {
+        if self.step > 0.0 {
+            self.step
+        } else {
+            (self.max - self.min) / 100.0
+        }
+    }
+    /// Length that the thumb can move along `axis`
+    fn track_length(&self, ctx: &WidgetContext) -> f32 {
+        let thumb_size = ctx.get(&self.thumb_id).map(|w| w.size).unwrap_or_default();
+        (ctx.size[self.axis] - thumb_size[self.axis]).max(0.0)
+    }
+    fn value_at_mouse(&self, ctx: &WidgetContext) -> f32 {
+        let mouse = ctx.gui.borrow().mouse_pos();
+        let thumb_size = ctx.get(&self.thumb_id).map(|w| w.size).unwrap_or_default();
+        let track_length = self.track_length(ctx);
+        let fraction = if track_length > 0.0 {
+            (mouse[self.axis] - ctx.pos[self.axis] - thumb_size[self.axis] / 2.0) / track_length
+        } else {
+            0.0
+        };
+        self.min + fraction * (self.max - self.min)
+    }
+}
+impl<Style: SliderStyle> Interactive for Slider<Style> {
+    fn init(&mut self, ctx: &mut WidgetContext) -> WidgetConfig {
+        self.thumb_id = ctx.insert_child(Container::new());
+        let (axis, cross) = (self.axis, self.axis.other());
+        ctx.access_child(self.thumb_id).configure(|config| {
+            let mut size = Vec2::zero();
+            size[axis] = DEFAULT_SLIDER_THICKNESS / 2.0;
+            size[cross] = DEFAULT_SLIDER_THICKNESS;
+            config.set_size(size.x, size.y);
+        });
+        let mut size = Vec2::zero();
+        size[axis] = DEFAULT_SLIDER_LENGTH;
+        size[cross] = DEFAULT_SLIDER_THICKNESS;
+        WidgetConfig::default().width(size.x).height(size.y)
+    }
+    fn update(&mut self, id: Id, local_events: Vec<Event>, ctx: &mut WidgetContext) {
+        let mut value = self.value;
+        for event in local_events {
+            if event.id != id {
+                continue;
+            }
+            match event.kind {
+                EventKind::Press {
+                    button: MouseButton::Left,
+                } => {
+                    value = self.value_at_mouse(ctx);
+                    ctx.gui.borrow_mut().capture_pointer(id);
+                }
+                EventKind::Drag { .. } => value = self.value_at_mouse(ctx),
+                EventKind::KeyPress { key, .. } => {
+                    let step = self.key_step();
+                    match key {
+                        VirtualKeyCode::Left | VirtualKeyCode::Up => value -= step,
+                        VirtualKeyCode::Right | VirtualKeyCode::Down => value += step,
+                        VirtualKeyCode::PageUp => value -= 10.0 * step,
+                        VirtualKeyCode::PageDown => value += 10.0 * step,
+                        VirtualKeyCode::Home => value = self.min,
+                        VirtualKeyCode::End => value = self.max,
+                        _ => (),
+                    }
+                }
+                _ => (),
+            }
+        }
+        let value = self.snap(value);
+        if value != self.value {
+            self.value = value;
+            ctx.push_event(EventKind::change(Self::value));
+        }
+
+        // Move the thumb
+        let fraction = if self.max > self.min {
+            (self.value - self.min) / (self.max - self.min)
+        } else {
+            0.0
+        };
+        let offset = fraction * self.track_length(ctx);
+        let placement = match self.axis {
+            Axis::X => Placement::fixed(offset, 0.0).y_anchor(Anchor::Center),
+            Axis::Y => Placement::fixed(0.0, offset).x_anchor(Anchor::Center),
+        };
+        ctx.access_child(self.thumb_id).configure(|config| {
+            config.set_placement(placement);
+        });
+    }
+    fn captures(&self) -> Capture {
+        Capture {
+            mouse: true,
+            keyboard: false,
+        }
+    }
+    fn focusable(&self) -> bool {
+        true
+    }
+}
+
+// -------
+// Lenses
+// -------
+
+use crate::widget::lenses::FirstChildLens;
+#[allow(non_upper_case_globals)]
+impl<Style> Slider<Style> {
+    pub const thumb: FirstChildLens = FirstChildLens;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_common::*;
+    use crate::{lens::*, vec::*, *};
+    use winit::event::VirtualKeyCode;
+    #[test]
+    fn test_slider() {
+        let mut gui = TestGui::new();
+        let id = gui.insert_in_root(Slider::new(0.0, 100.0).with_step(10.0));
+        gui.update();
+        gui.update();
+        let value = |gui: &mut TestGui| *gui.access(id).chain(Slider::value).get();
+        // The thumb is 10 wide, so its center moves between x=5 and x=145
+        let (pos, size) = (gui.get(id).pos, gui.get(id).size);
+        assert_eq!(size, Vec2::new(150.0, 20.0));
+        let y = pos.y + 10.0;
+
+        // Pressing moves the thumb to the mouse, rounding to the closest step
+        let (events, _) = gui.press(Vec2::new(pos.x + 5.0 + 72.0, y));
+        assert!(events.contains(&Event::change(id, Slider::value)));
+        assert_eq!(value(&mut gui), 50.0);
+        gui.update();
+        assert_eq!(gui.access(id).chain(Slider::thumb).get().rel_pos.x, 70.0);
+
+        // Dragging continues outside the slider
+        gui.move_mouse(Vec2::new(pos.x + 1000.0, y + 1000.0));
+        assert_eq!(value(&mut gui), 100.0);
+        gui.move_mouse(Vec2::new(pos.x + 5.0 + 14.0, y + 1000.0));
+        assert_eq!(value(&mut gui), 10.0);
+        gui.release();
+        gui.move_mouse(Vec2::new(pos.x + 5.0 + 140.0, y));
+        assert_eq!(value(&mut gui), 10.0);
+
+        // Keyboard
+        gui.press_key(VirtualKeyCode::Right);
+        assert_eq!(value(&mut gui), 20.0);
+        gui.press_key(VirtualKeyCode::PageDown);
+        assert_eq!(value(&mut gui), 100.0);
+        gui.press_key(VirtualKeyCode::Home);
+        assert_eq!(value(&mut gui), 0.0);
+    }
+}
@@ -8,6 +8,11 @@ pub trait TextFieldStyle: StyleBound {}
 pub struct TextField<Style> {
     #[lens]
     pub text: String,
+    /// If true, the text is broken into several lines to fit the width of the widget (see
+    /// `TextCalculator::wrap_lines`). The width has to be given by `SizeHint::External` or
+    /// `SizeHint::Flex`; the height follows from the number of lines.
+    #[lens]
+    pub wrap: bool,
     // TODO (idea): lens that is not a LeafLens but can be further chained with fields of Style
     pub style: Style,
 }
@@ -15,9 +20,14 @@ impl<Style: TextFieldStyle> TextField<Style> {
     pub fn new(text: String) -> TextField<Style> {
         TextField {
             text,
+            wrap: false,
             style: Style::default(),
         }
     }
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
 }
 impl<Style: TextFieldStyle> Interactive for TextField<Style> {
     fn init(&mut self, ctx: &mut WidgetContext) -> WidgetConfig {
@@ -29,7 +39,25 @@ impl<Style: TextFieldStyle> Interactive for TextField<Style> {
             keyboard: false,
         }
     }
+    fn text(&self) -> Option<&str> {
+        Some(&self.text)
+    }
+    fn wraps_text(&self) -> bool {
+        self.wrap
+    }
     fn determine_size(&self, drawer: &mut dyn TextCalculator) -> Option<Vec2> {
         Some(drawer.text_size(&self.text))
     }
+    fn determine_size_with_width(
+        &self,
+        drawer: &mut dyn TextCalculator,
+        width: Option<f32>,
+    ) -> Option<Vec2> {
+        if !self.wrap {
+            return self.determine_size(drawer);
+        }
+        let lines = drawer.wrap_lines(&self.text, width.unwrap_or(std::f32::INFINITY));
+        let width = lines.iter().map(|line| line.width).fold(0.0, f32::max);
+        Some(Vec2::new(width, lines.len() as f32 * drawer.line_height()))
+    }
 }
@@ -0,0 +1,381 @@
+use super::*;
+use crate::*;
+use interactive::*;
+use winit::event::{ModifiersState, MouseButton, VirtualKeyCode};
+
+pub trait TextInputStyle: StyleBound {
+    /// Style of contained text field
+    type TextField: TextFieldStyle;
+}
+
+/// Single-line editable text. Receives keyboard input while focused (see `Gui::focus`).
+///
+/// Positions in the text (`cursor` and `selection`) count characters, not bytes.
+/// The contained `TextField` (`TextInput::text_field`) shows `text`, or `placeholder` if `text` is
+/// empty.
+#[derive(LensInternal, Debug)]
+pub struct TextInput<Style> {
+    #[lens]
+    pub text: String,
+    /// Position of the caret: the number of characters before it
+    #[lens]
+    pub cursor: usize,
+    /// If `Some`, the other end of the selection. The selected text lies between `selection` and
+    /// `cursor` (see `TextInput::selection_range`).
+    #[lens]
+    pub selection: Option<usize>,
+    /// Shown when `text` is empty
+    #[lens]
+    pub placeholder: String,
+    pub style: Style,
+
+    text_field_id: Id,
+}
+impl<Style: TextInputStyle> TextInput<Style> {
+    pub fn new() -> TextInput<Style> {
+        TextInput {
+            text: String::new(),
+            cursor: 0,
+            selection: None,
+            placeholder: String::new(),
+            style: Style::default(),
+            text_field_id: 0,
+        }
+    }
+    pub fn with_placeholder(mut self, placeholder: String) -> Self {
+        self.placeholder = placeholder;
+        self
+    }
+    pub fn text_field_id(&self) -> Id {
+        self.text_field_id
+    }
+    /// The selected characters as `(start, end)`, if any are selected
+    pub fn selection_range(&self) -> Option<(usize, usize)> {
+        self.selection
+            .filter(|selection| *selection != self.cursor)
+            .map(|selection| (selection.min(self.cursor), selection.max(self.cursor)))
+    }
+
+    fn len(&self) -> usize {
+        self.text.chars().count()
+    }
+    /// Byte index of the character at `index`
+    fn byte_index(&self, index: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(index)
+            .map(|(i, _)| i)
+            .unwrap_or_else(|| self.text.len())
+    }
+    /// Remove the selected text, if any. Returns true if something was removed.
+    fn delete_selection(&mut self) -> bool {
+        if let Some((start, end)) = self.selection_range() {
+            let range = self.byte_index(start)..self.byte_index(end);
+            self.text.replace_range(range, "");
+            self.cursor = start;
+            self.selection = None;
+            true
+        } else {
+            self.selection = None;
+            false
+        }
+    }
+    fn insert(&mut self, c: char) {
+        self.delete_selection();
+        let i = self.byte_index(self.cursor);
+        self.text.insert(i, c);
+        self.cursor += 1;
+    }
+    /// Delete from the cursor to `to`, unless there is a selection, which is then deleted instead
+    fn delete_to(&mut self, to: usize) {
+        if !self.delete_selection() {
+            let (start, end) = (to.min(self.cursor), to.max(self.cursor));
+            let range = self.byte_index(start)..self.byte_index(end);
+            self.text.replace_range(range, "");
+            self.cursor = start;
+        }
+    }
+    /// Move the cursor to `to`. If `select`, the selection is extended, otherwise it is removed.
+    fn move_to(&mut self, to: usize, select: bool) {
+        if select {
+            if self.selection.is_none() {
+                self.selection = Some(self.cursor);
+            }
+        } else {
+            self.selection = None;
+        }
+        self.cursor = to;
+    }
+    /// Start of the word before `index`
+    fn prev_word(&self, index: usize) -> usize {
+        let chars = self.text.chars().collect::<Vec<_>>();
+        let mut i = index;
+        while i > 0 && chars[i - 1].is_whitespace() {
+            i -= 1;
+        }
+        while i > 0 && !chars[i - 1].is_whitespace() {
+            i -= 1;
+        }
+        i
+    }
+    /// End of the word after `index`
+    fn next_word(&self, index: usize) -> usize {
+        let chars = self.text.chars().collect::<Vec<_>>();
+        let mut i = index;
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+        while i < chars.len() && !chars[i].is_whitespace() {
+            i += 1;
+        }
+        i
+    }
+    /// The character boundary closest to the mouse, using the text calculator
+    fn index_at_mouse(&self, ctx: &mut WidgetContext) -> usize {
+        if self.text.is_empty() {
+            return 0;
+        }
+        let text_pos = ctx
+            .get(&self.text_field_id)
+            .map(|w| w.pos)
+            .unwrap_or(ctx.pos);
+        let mut gui = ctx.gui.borrow_mut();
+        let x = gui.mouse_pos().x - text_pos.x;
+        gui.text_calc(ctx.layer).index_at_point(&self.text, x)
+    }
+    fn key_press(&mut self, key: VirtualKeyCode, modifiers: ModifiersState) {
+        let word = modifiers.ctrl();
+        let select = modifiers.shift();
+        match key {
+            VirtualKeyCode::Left => {
+                let to = if word {
+                    self.prev_word(self.cursor)
+                } else {
+                    match self.selection_range() {
+                        Some((start, _)) if !select => start,
+                        _ => self.cursor.saturating_sub(1),
+                    }
+                };
+                self.move_to(to, select);
+            }
+            VirtualKeyCode::Right => {
+                let to = if word {
+                    self.next_word(self.cursor)
+                } else {
+                    match self.selection_range() {
+                        Some((_, end)) if !select => end,
+                        _ => (self.cursor + 1).min(self.len()),
+                    }
+                };
+                self.move_to(to, select);
+            }
+            VirtualKeyCode::Home => self.move_to(0, select),
+            VirtualKeyCode::End => self.move_to(self.len(), select),
+            VirtualKeyCode::Back => {
+                let to = if word {
+                    self.prev_word(self.cursor)
+                } else {
+                    self.cursor.saturating_sub(1)
+                };
+                self.delete_to(to);
+            }
+            VirtualKeyCode::Delete => {
+                let to = if word {
+                    self.next_word(self.cursor)
+                } else {
+                    (self.cursor + 1).min(self.len())
+                };
+                self.delete_to(to);
+            }
+            VirtualKeyCode::A if modifiers.ctrl() => {
+                self.selection = Some(0);
+                self.cursor = self.len();
+            }
+            _ => (),
+        }
+    }
+}
+
+impl<Style: TextInputStyle> Interactive for TextInput<Style> {
+    fn init(&mut self, ctx: &mut WidgetContext) -> WidgetConfig {
+        self.text_field_id =
+            ctx.insert_child(TextField::<Style::TextField>::new(self.placeholder.clone()));
+        WidgetConfig::default()
+            .size_hint(SizeHint::Minimize, SizeHint::Minimize)
+            .layout_direction(Axis::X)
+            .layout_cross_align(Anchor::Center)
+            .padding(4.0, 4.0, 6.0, 6.0)
+            .height(DEFAULT_BUTTON_HEIGHT)
+            .min_width(100.0)
+    }
+    fn update(&mut self, id: Id, local_events: Vec<Event>, ctx: &mut WidgetContext) {
+        let (text, cursor, selection) = (self.text.clone(), self.cursor, self.selection);
+
+        // The text may have been changed through a lens
+        self.cursor = self.cursor.min(self.len());
+        self.selection = self.selection.map(|selection| selection.min(self.len()));
+
+        for event in local_events {
+            if event.id != id {
+                continue;
+            }
+            match event.kind {
+                EventKind::Char(c) => self.insert(c),
+                EventKind::KeyPress { key, modifiers } => self.key_press(key, modifiers),
+                EventKind::Press {
+                    button: MouseButton::Left,
+                } => {
+                    // Place the caret, and start selecting
+                    self.cursor = self.index_at_mouse(ctx);
+                    self.selection = Some(self.cursor);
+                    ctx.gui.borrow_mut().capture_pointer(id);
+                }
+                EventKind::Drag { .. } => self.cursor = self.index_at_mouse(ctx),
+                _ => (),
+            }
+        }
+        let selecting = {
+            let gui = ctx.gui.borrow();
+            gui.pointer_capture() == Some(id) && gui.mouse_down()
+        };
+        if self.selection == Some(self.cursor) && !selecting {
+            self.selection = None;
+        }
+        if ctx.gui.borrow().focused() != Some(id) {
+            self.selection = None;
+        }
+
+        if self.text != text {
+            ctx.push_event(EventKind::change(Self::text));
+        }
+        if self.cursor != cursor {
+            ctx.push_event(EventKind::change(Self::cursor));
+        }
+        if self.selection != selection {
+            ctx.push_event(EventKind::change(Self::selection));
+        }
+
+        let shown = if self.text.is_empty() {
+            self.placeholder.clone()
+        } else {
+            self.text.clone()
+        };
+        ctx.access_child(self.text_field_id)
+            .chain(TextField::<Style::TextField>::text)
+            .put(shown);
+    }
+    fn captures(&self) -> Capture {
+        Capture {
+            mouse: true,
+            keyboard: true,
+        }
+    }
+    fn focusable(&self) -> bool {
+        true
+    }
+}
+
+// -------
+// Lenses
+// -------
+
+use crate::widget::lenses::FirstChildLens;
+#[allow(non_upper_case_globals)]
+impl<Style> TextInput<Style> {
+    pub const text_field: FirstChildLens = FirstChildLens;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_common::*;
+    use crate::{lens::*, *};
+    use winit::event::VirtualKeyCode::*;
+    #[test]
+    fn test_text_input() {
+        let mut gui = TestGui::new();
+        let id = gui.insert_in_root(TextInput::new().with_placeholder("Name".to_string()));
+        gui.update();
+        let shown = |gui: &mut TestGui| {
+            gui.access(id)
+                .chain(TextInput::text_field)
+                .chain(TextField::text)
+                .get()
+                .clone()
+        };
+        assert_eq!(shown(&mut gui), "Name");
+
+        // Typing requires focus
+        gui.type_text("x");
+        assert_eq!(gui.access(id).chain(TextInput::text).get(), "");
+        gui.focus(id);
+        let (events, capture) = gui.type_text("hello wörld");
+        assert!(capture.keyboard);
+        assert!(events.contains(&Event::change(id, TextInput::text)));
+        assert!(events.contains(&Event::change(id, TextInput::cursor)));
+        assert_eq!(gui.access(id).chain(TextInput::text).get(), "hello wörld");
+        assert_eq!(*gui.access(id).chain(TextInput::cursor).get(), 11);
+        gui.update();
+        assert_eq!(shown(&mut gui), "hello wörld");
+
+        // Word-wise movement and selection
+        gui.press_key(Home);
+        gui.press_key(LControl);
+        gui.press_key(Right);
+        gui.release_key(LControl);
+        assert_eq!(*gui.access(id).chain(TextInput::cursor).get(), 5);
+        gui.press_key(LShift);
+        gui.press_key(End);
+        gui.release_key(LShift);
+        assert_eq!(*gui.access(id).chain(TextInput::selection).get(), Some(5));
+        assert_eq!(*gui.access(id).chain(TextInput::cursor).get(), 11);
+
+        // Typing replaces the selection
+        gui.type_text("!");
+        assert_eq!(gui.access(id).chain(TextInput::text).get(), "hello!");
+        assert_eq!(*gui.access(id).chain(TextInput::selection).get(), None);
+        gui.press_key(Left);
+        gui.press_key(Back);
+        gui.press_key(Delete);
+        assert_eq!(gui.access(id).chain(TextInput::text).get(), "hell");
+        assert_eq!(*gui.access(id).chain(TextInput::cursor).get(), 4);
+
+        // Deleting everything shows the placeholder again
+        gui.press_key(LControl);
+        gui.press_key(Back);
+        gui.release_key(LControl);
+        gui.update();
+        assert_eq!(gui.access(id).chain(TextInput::text).get(), "");
+        assert_eq!(shown(&mut gui), "Name");
+    }
+
+    #[test]
+    fn test_text_input_mouse() {
+        let mut gui = TestGui::new();
+        let id = gui.insert_in_root(TextInput::new());
+        gui.access(id)
+            .chain(TextInput::text)
+            .put("hello world".to_string());
+        gui.update();
+        gui.update();
+        let text_x = gui.get(id).children().values().next().unwrap().pos.x;
+        let y = gui.get(id).pos.y + 10.0;
+
+        // Click to place the caret (rounding to the closest character boundary)
+        let (events, _) = gui.press(Vec2::new(text_x + 23.0, y));
+        gui.release();
+        assert!(events.contains(&Event::new(id, EventKind::Focus)));
+        assert_eq!(*gui.access(id).chain(TextInput::cursor).get(), 2);
+        assert_eq!(*gui.access(id).chain(TextInput::selection).get(), None);
+
+        // Drag to select
+        gui.press(Vec2::new(text_x + 38.0, y));
+        gui.move_mouse(Vec2::new(text_x + 71.0, y));
+        gui.release();
+        assert_eq!(*gui.access(id).chain(TextInput::selection).get(), Some(4));
+        assert_eq!(*gui.access(id).chain(TextInput::cursor).get(), 7);
+        assert_eq!(
+            gui.access(id).chain(TextInput::text).get().get(4..7),
+            Some("o w")
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use crate::*;
+use slog::Logger;
+
+/// One primitive to draw, produced by `DisplayListDrawer`.
+/// Positions are in the coordinate system of the widgets (see `GuiDrawer::transform_mouse`).
+#[derive(Clone, Debug, PartialEq)]
+pub enum DrawCommand {
+    /// The area of a widget. Backends can look up the widget (`Gui::get`) to decide how to draw
+    /// it.
+    Rect {
+        id: Id,
+        rect: Rect,
+    },
+    /// One line of the text of a widget (see `Interactive::text`), with its top left corner at
+    /// `pos`
+    Text {
+        id: Id,
+        pos: Vec2,
+        text: String,
+    },
+    /// The image of a widget (see `Interactive::image`), stretched over `rect`
+    Image {
+        id: Id,
+        rect: Rect,
+        source: String,
+    },
+    /// Clip all following commands to `rect`, until the next `PopClip`.
+    /// Clip commands are never nested: `rect` already is the intersection of all clipping
+    /// ancestors (see `WidgetConfig::clip_children`).
+    PushClip(Rect),
+    PopClip,
+}
+
+/// Walks the widget tree in every update and produces a list of `DrawCommand`s, which a backend
+/// only has to draw in order.
+///
+/// Commands are ordered by `Widget::layer`, and within a layer parents come before their
+/// children. The root widget is not drawn.
+pub struct DisplayListDrawer<C> {
+    text_calc: C,
+    window_size: Vec2,
+    commands: Vec<DrawCommand>,
+}
+impl<C: TextCalculator + Clone> DisplayListDrawer<C> {
+    /// `text_calc` should measure text like the backend draws it
+    pub fn new(text_calc: C, window_size: Vec2) -> Self {
+        DisplayListDrawer {
+            text_calc,
+            window_size,
+            commands: Vec::new(),
+        }
+    }
+    pub fn set_window_size(&mut self, window_size: Vec2) {
+        self.window_size = window_size;
+    }
+    /// Commands produced by the last update
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+    /// Walk the tree of `root` and replace `commands`. Called by `update`; drawers that are built
+    /// on top of this one can call it themselves.
+    pub fn update_commands(&mut self, root: &Widget) {
+        let mut widgets = Vec::new();
+        for child in root.children().values() {
+            pre_order(child, &mut widgets);
+        }
+        // (stable, so tree order is kept within each layer)
+        widgets.sort_by_key(|w| w.layer);
+
+        let mut commands = Vec::new();
+        let mut clip = None;
+        for w in widgets {
+            if w.clip != clip {
+                if clip.is_some() {
+                    commands.push(DrawCommand::PopClip);
+                }
+                if let Some(rect) = w.clip {
+                    commands.push(DrawCommand::PushClip(rect));
+                }
+                clip = w.clip;
+            }
+            self.widget_commands(w, &mut commands);
+        }
+        if clip.is_some() {
+            commands.push(DrawCommand::PopClip);
+        }
+        self.commands = commands;
+    }
+    pub fn text_calc_mut(&mut self) -> &mut C {
+        &mut self.text_calc
+    }
+    fn widget_commands(&mut self, w: &Widget, commands: &mut Vec<DrawCommand>) {
+        let id = w.get_id();
+        let rect = Rect::from_pos_size(w.pos, w.size);
+        commands.push(DrawCommand::Rect { id, rect });
+        if let Some(source) = w.image() {
+            commands.push(DrawCommand::Image {
+                id,
+                rect,
+                source: source.to_string(),
+            });
+        }
+        if let Some(text) = w.text() {
+            let max_width = if w.wraps_text() {
+                w.size.x
+            } else {
+                std::f32::INFINITY
+            };
+            let line_height = self.text_calc.line_height();
+            for (i, line) in self
+                .text_calc
+                .wrap_lines(text, max_width)
+                .iter()
+                .enumerate()
+            {
+                if line.start == line.end {
+                    continue;
+                }
+                commands.push(DrawCommand::Text {
+                    id,
+                    pos: w.pos + Vec2::new(0.0, i as f32 * line_height),
+                    text: text[line.start..line.end].to_string(),
+                });
+            }
+        }
+    }
+}
+impl<C: TextCalculator + Clone> GuiDrawer for DisplayListDrawer<C> {
+    type Context = ();
+    type Calculator = C;
+    fn window_size(&self, _ctx: &mut Self::Context) -> Vec2 {
+        self.window_size
+    }
+    fn transform_mouse(&self, m: Vec2, _ctx: &mut Self::Context) -> Vec2 {
+        m
+    }
+    fn update(
+        &mut self,
+        gui: &Gui<Self>,
+        _events: &[Event],
+        _log: Logger,
+        _ctx: &mut Self::Context,
+    ) -> Vec<WidgetOp> {
+        self.update_commands(&gui.root);
+        Vec::new()
+    }
+    fn text_calc(&mut self, _layer: u32, _ctx: &mut Self::Context) -> Self::Calculator {
+        self.text_calc.clone()
+    }
+}
+
+fn pre_order<'a>(w: &'a Widget, widgets: &mut Vec<&'a Widget>) {
+    widgets.push(w);
+    for child in w.children().values() {
+        pre_order(child, widgets);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_common::*;
+    #[test]
+    fn test_display_list() {
+        let drawer = DisplayListDrawer::new(NoTextCalculator, Vec2::new(400.0, 300.0));
+        let mut gui = TestGui::with_drawer(drawer, ());
+        let popup = gui.insert_in_root(crate::default::Container::new());
+        gui.access(popup).configure(|config| {
+            config.set_placement(Placement::fixed(300.0, 0.0));
+        });
+        let popup_text = gui
+            .get_mut(popup)
+            .insert_child(TextField::new("On top".to_string()));
+        let text = gui.insert_in_root(TextField::new("Hello  world\nagain".to_string()));
+        let image = gui.insert_in_root(Image::new("icon.png".to_string()).with_size(32.0, 16.0));
+        let area = gui.insert_in_root(ScrollArea::new());
+        gui.access(area).configure(|config| {
+            config.set_size(100.0, 50.0);
+        });
+        let button = gui
+            .access(area)
+            .chain(ScrollArea::content)
+            .get_widget_mut()
+            .insert_child(Button::new());
+        gui.update();
+        gui.update();
+        let commands = gui.drawer().commands().to_vec();
+        let position = |command: &DrawCommand| {
+            commands
+                .iter()
+                .position(|c| c == command)
+                .unwrap_or_else(|| panic!("{:?} not in {:#?}", command, commands))
+        };
+        let rect = |id: Id| DrawCommand::Rect {
+            id,
+            rect: Rect::from_pos_size(gui.get(id).pos, gui.get(id).size),
+        };
+
+        // One text command per line
+        let pos = gui.get(text).pos;
+        position(&DrawCommand::Text {
+            id: text,
+            pos,
+            text: "Hello  world".to_string(),
+        });
+        position(&DrawCommand::Text {
+            id: text,
+            pos: pos + Vec2::new(0.0, 10.0),
+            text: "again".to_string(),
+        });
+        position(&DrawCommand::Image {
+            id: image,
+            rect: Rect::from_pos_size(gui.get(image).pos, Vec2::new(32.0, 16.0)),
+            source: "icon.png".to_string(),
+        });
+
+        // The content of the scroll area is clipped
+        let clip = Rect::from_pos_size(gui.get(area).pos, Vec2::new(100.0, 50.0));
+        let push_clip = position(&DrawCommand::PushClip(clip));
+        assert!(position(&rect(area)) < push_clip);
+        assert!(push_clip < position(&rect(button)));
+        assert_eq!(
+            commands[position(&rect(button)) + 2..].first(),
+            Some(&DrawCommand::PopClip)
+        );
+
+        // Higher layers come last, even though `popup` is the first child of the root
+        assert_eq!(commands[commands.len() - 3], rect(popup));
+        assert_eq!(commands[commands.len() - 2], rect(popup_text));
+    }
+}
@@ -0,0 +1,239 @@
+//! Trait definition for interoperability between Gui and a renderer/drawer.
+//!
+//! It is encouraged to implement custom drawers for applications that need their
+//! own GUI look (such as games).
+//!
+//! The following modules provide default drawers for when you just want to get something working
+//! (before starting thinking about the look). Each module supports one specific rendering backend.
+//!
+//! - [DisplayListDrawer]: produces a list of renderer-agnostic draw commands, for backends to
+//!   consume.
+//! - [SvgDrawer]: renders to an SVG document, without a window.
+//! - [RasterDrawer]: rasterizes into an image in memory, without a window or GPU.
+//! - [TuiDrawer]: draws on a grid of character cells, for terminals.
+
+use crate::*;
+use slog::Logger;
+
+mod display_list;
+mod raster;
+mod svg;
+mod tui;
+
+pub use display_list::*;
+pub use raster::*;
+pub use svg::*;
+pub use tui::*;
+
+pub trait GuiDrawer: Sized {
+    type Context;
+    type Calculator: TextCalculator;
+    fn window_size(&self, ctx: &mut Self::Context) -> Vec2;
+    fn transform_mouse(&self, m: Vec2, ctx: &mut Self::Context) -> Vec2;
+    fn update(
+        &mut self,
+        gui: &Gui<Self>,
+        events: &[Event],
+        log: Logger,
+        ctx: &mut Self::Context,
+    ) -> Vec<WidgetOp>;
+
+    /// The text calculator for widgets on `layer`. `Gui` takes one for every layer in use, and
+    /// takes it again on `WidgetOp::Remeasure`.
+    fn text_calc(&mut self, layer: u32, ctx: &mut Self::Context) -> Self::Calculator;
+}
+
+/// One line of wrapped text (see `TextCalculator::wrap_lines`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextLine {
+    /// Byte index of the first character of the line
+    pub start: usize,
+    /// Byte index after the last character of the line (excluding the whitespace that it was
+    /// broken at)
+    pub end: usize,
+    /// Width of the line as measured by `TextCalculator::text_size`
+    pub width: f32,
+}
+
+pub trait TextCalculator: 'static + std::fmt::Debug {
+    fn text_size(&mut self, text: &str) -> Vec2;
+
+    /// Distance between the top of two consecutive lines
+    fn line_height(&mut self) -> f32 {
+        self.text_size("X").y
+    }
+    /// Breaks `text` into lines that are at most `max_width` wide, at word boundaries (whitespace)
+    /// and at newlines. A word that is wider than `max_width` gets a line of its own.
+    fn wrap_lines(&mut self, text: &str, max_width: f32) -> Vec<TextLine> {
+        let mut lines = Vec::new();
+        let mut paragraph_start = 0;
+        for paragraph in text.split('\n') {
+            // Byte ranges of words in the paragraph
+            let mut words = Vec::new();
+            let mut word_start = None;
+            for (i, c) in paragraph.char_indices() {
+                match (c.is_whitespace(), word_start) {
+                    (false, None) => word_start = Some(i),
+                    (true, Some(start)) => {
+                        words.push((paragraph_start + start, paragraph_start + i));
+                        word_start = None;
+                    }
+                    _ => (),
+                }
+            }
+            if let Some(start) = word_start {
+                words.push((paragraph_start + start, paragraph_start + paragraph.len()));
+            }
+
+            let mut line: Option<(usize, usize)> = None;
+            for (word_start, word_end) in words {
+                line = match line {
+                    Some((start, end)) if self.text_size(&text[start..word_end]).x > max_width => {
+                        let width = self.text_size(&text[start..end]).x;
+                        lines.push(TextLine { start, end, width });
+                        Some((word_start, word_end))
+                    }
+                    Some((start, _)) => Some((start, word_end)),
+                    None => Some((word_start, word_end)),
+                };
+            }
+            let (start, end) = line.unwrap_or((paragraph_start, paragraph_start));
+            let width = self.text_size(&text[start..end]).x;
+            lines.push(TextLine { start, end, width });
+
+            paragraph_start += paragraph.len() + 1;
+        }
+        lines
+    }
+
+    /// X offset of every character boundary in `text`, from the left edge of the text.
+    /// Has one more element than there are characters: the first is always zero, and the last is
+    /// the width of the whole text.
+    /// The default implementation measures every prefix with `text_size`; override it if the
+    /// backend provides a faster way.
+    fn char_offsets(&mut self, text: &str) -> Vec<f32> {
+        std::iter::once(0.0)
+            .chain(
+                text.char_indices()
+                    .map(|(i, c)| i + c.len_utf8())
+                    .map(|end| self.text_size(&text[..end]).x)
+                    .collect::<Vec<_>>(),
+            )
+            .collect()
+    }
+    /// The character boundary closest to the x coordinate `x` (relative to the left edge of the
+    /// text), as the number of characters before it.
+    fn index_at_point(&mut self, text: &str, x: f32) -> usize {
+        let offsets = self.char_offsets(text);
+        offsets
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                (*a - x)
+                    .abs()
+                    .partial_cmp(&(*b - x).abs())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    }
+    /// X coordinate (relative to the left edge of the text) of the caret placed before the
+    /// character at `index`. Indices past the end give the width of the text.
+    fn point_at_index(&mut self, text: &str, index: usize) -> f32 {
+        let offsets = self.char_offsets(text);
+        offsets[index.min(offsets.len() - 1)]
+    }
+}
+
+/*
+pub trait ContextFreeGuiDrawer {
+    fn text_size(&mut self, text: &str) -> Vec2;
+}
+
+/// Contains references to the GuiDrawer as well as its context, and thus provides a `GuiDrawer`
+/// interface where `type Context = ()` (kinda erases the context from the interface and moves it
+/// internally).
+/// This struct is used primarily as a parameter to functions of `trait Interactive`.
+pub struct GuiDrawerWithContext<'a, 'b, D: GuiDrawer> {
+    drawer: &'a D,
+    ctx: &'b mut D::Context,
+}
+impl<'a, 'b, D: GuiDrawer> ContextFreeGuiDrawer for GuiDrawerWithContext<'a, 'b, D> {
+    /// Determine size of rendered text without rendering it.
+    fn text_size(&mut self, text: &str) -> Vec2 {
+        self.drawer.text_size(text, self.ctx)
+    }
+}
+*/
+
+/// Text calculator used with `NoDrawer` - simple 10.0 times the number of characters
+#[derive(Debug, Clone)]
+pub struct NoTextCalculator;
+impl TextCalculator for NoTextCalculator {
+    fn text_size(&mut self, text: &str) -> Vec2 {
+        Vec2::new(10.0 * text.chars().count() as f32, 10.0)
+    }
+    fn char_offsets(&mut self, text: &str) -> Vec<f32> {
+        (0..=text.chars().count())
+            .map(|i| 10.0 * i as f32)
+            .collect()
+    }
+}
+/// Empty implementor of GuiDrawer, for a headless Gui.
+/// Note: Text size and window size are always zero.
+pub struct NoDrawer;
+impl GuiDrawer for NoDrawer {
+    type Context = ();
+    type Calculator = NoTextCalculator;
+    fn window_size(&self, _ctx: &mut Self::Context) -> Vec2 {
+        Vec2::zero()
+    }
+    fn transform_mouse(&self, m: Vec2, _ctx: &mut Self::Context) -> Vec2 {
+        m
+    }
+    fn update(
+        &mut self,
+        _gui: &Gui<Self>,
+        _events: &[Event],
+        _log: Logger,
+        _ctx: &mut Self::Context,
+    ) -> Vec<WidgetOp> {
+        Vec::new()
+    }
+    fn text_calc(&mut self, _layer: u32, _ctx: &mut Self::Context) -> Self::Calculator {
+        NoTextCalculator
+    }
+}
+
+/// An operation that a drawer asks `Gui` to apply, by returning it from `GuiDrawer::update`.
+/// `Gui` applies the operations before `Gui::update` returns, and the events that they generate
+/// (such as `EventKind::Change` for `Widget::size`) are returned along with the other events.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WidgetOp {
+    /// Measure a widget again, for example because a font finished loading: the text calculator
+    /// for the layer of the widget is taken from the drawer again (see `GuiDrawer::text_calc`),
+    /// and the layout is updated. Widgets on other layers keep their text calculators.
+    Remeasure(Id),
+    /// Set the intrinsic size of a widget (see `Interactive::set_intrinsic_size`), for example to
+    /// the size of an image that finished loading, and update the layout.
+    SetIntrinsicSize(Id, Vec2),
+    /// Ask for another update in the next frame, even if there is no input (see
+    /// `Gui::redraw_requested`).
+    RequestRedraw,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn test_no_text_calculator_non_ascii() {
+        let mut calc = NoTextCalculator;
+        // Measured in characters, not bytes
+        assert_eq!(calc.text_size("wörld"), Vec2::new(50.0, 10.0));
+        let offsets = calc.char_offsets("wörld");
+        assert_eq!(offsets, vec![0.0, 10.0, 20.0, 30.0, 40.0, 50.0]);
+        assert_eq!(offsets.last(), Some(&calc.text_size("wörld").x));
+        assert_eq!(calc.point_at_index("wörld", 5), 50.0);
+        assert_eq!(calc.index_at_point("wörld", 48.0), 5);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::*;
+
+/// Color with red, green, blue and alpha components
+pub type Rgba = [u8; 4];
+
+/// An image in memory with 8-bit RGBA pixels, row by row from the top left corner
+#[derive(Clone, Debug, PartialEq)]
+pub struct RgbaImage {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+impl RgbaImage {
+    /// Image filled with `color`
+    pub fn new(width: u32, height: u32, color: Rgba) -> RgbaImage {
+        RgbaImage {
+            width,
+            height,
+            data: color
+                .iter()
+                .cloned()
+                .cycle()
+                .take((width * height * 4) as usize)
+                .collect(),
+        }
+    }
+    /// Image from the raw pixel data, which has to be `width * height * 4` bytes long
+    pub fn from_raw(width: u32, height: u32, data: Vec<u8>) -> Option<RgbaImage> {
+        if data.len() == (width * height * 4) as usize {
+            Some(RgbaImage {
+                width,
+                height,
+                data,
+            })
+        } else {
+            None
+        }
+    }
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    pub fn as_raw(&self) -> &[u8] {
+        &self.data
+    }
+    /// Panics if `(x, y)` is outside the image
+    pub fn get_pixel(&self, x: u32, y: u32) -> Rgba {
+        let i = self.index(x, y);
+        [
+            self.data[i],
+            self.data[i + 1],
+            self.data[i + 2],
+            self.data[i + 3],
+        ]
+    }
+    /// Panics if `(x, y)` is outside the image
+    pub fn put_pixel(&mut self, x: u32, y: u32, color: Rgba) {
+        let i = self.index(x, y);
+        self.data[i..i + 4].copy_from_slice(&color);
+    }
+    /// Fill the pixels whose centers are inside both `rect` and `clip` with `color`.
+    /// (Colors are not blended; `color` replaces the previous color)
+    pub fn fill_rect(&mut self, rect: Rect, clip: Rect, color: Rgba) {
+        let (x0, x1) = self.pixel_range(
+            rect.min.x.max(clip.min.x),
+            rect.max.x.min(clip.max.x),
+            self.width,
+        );
+        let (y0, y1) = self.pixel_range(
+            rect.min.y.max(clip.min.y),
+            rect.max.y.min(clip.max.y),
+            self.height,
+        );
+        for y in y0..y1 {
+            for x in x0..x1 {
+                self.put_pixel(x, y, color);
+            }
+        }
+    }
+    /// Draw the one pixel wide outline of `rect`, inside `rect` and `clip`, with `color`
+    pub fn stroke_rect(&mut self, rect: Rect, clip: Rect, color: Rgba) {
+        let Rect { min, max } = rect;
+        let edges = [
+            Rect::new(min, Vec2::new(max.x, min.y + 1.0)),
+            Rect::new(Vec2::new(min.x, max.y - 1.0), max),
+            Rect::new(min, Vec2::new(min.x + 1.0, max.y)),
+            Rect::new(Vec2::new(max.x - 1.0, min.y), max),
+        ];
+        for edge in edges.iter() {
+            self.fill_rect(*edge, clip, color);
+        }
+    }
+    /// Pixels from `min` (inclusive) to `max` (exclusive) whose centers are between `min` and
+    /// `max`, limited to the image
+    fn pixel_range(&self, min: f32, max: f32, len: u32) -> (u32, u32) {
+        let clamp = |v: f32| (v - 0.5).ceil().max(0.0).min(len as f32) as u32;
+        (clamp(min), clamp(max).max(clamp(min)))
+    }
+    fn index(&self, x: u32, y: u32) -> usize {
+        assert!(x < self.width && y < self.height, "pixel outside of image");
+        ((y * self.width + x) * 4) as usize
+    }
+}
@@ -0,0 +1,184 @@
+use crate::*;
+use slog::Logger;
+
+mod font;
+mod image;
+
+pub use font::*;
+pub use image::*;
+
+/// How `RasterDrawer` draws the rectangle of a widget
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BoxStyle {
+    pub fill: Option<Rgba>,
+    /// Color of a one pixel wide border inside the rectangle
+    pub border: Option<Rgba>,
+}
+
+/// The style of `RasterDrawer` unless another is given with `RasterDrawer::with_style`:
+/// widgets that capture the mouse (such as buttons) are filled and get a border, and are
+/// brighter while hovered. Other widgets only show their text or image.
+pub fn default_box_style(w: &Widget) -> BoxStyle {
+    if w.captures().mouse {
+        BoxStyle {
+            fill: Some(if w.hover() {
+                [0xE0, 0xE0, 0xE0, 0xFF]
+            } else {
+                [0xC8, 0xC8, 0xC8, 0xFF]
+            }),
+            border: Some([0x40, 0x40, 0x40, 0xFF]),
+        }
+    } else {
+        BoxStyle::default()
+    }
+}
+
+/// Rasterizes the widget tree into an in-memory `RgbaImage` in every update, without any window
+/// or GPU, for example to test the appearance of the GUI in CI (see
+/// `test_common::assert_golden_image`).
+///
+/// Text is drawn with the built-in `BitmapFont`, which is also the `TextCalculator`. Since images
+/// are not loaded, they are drawn as rectangles of `image_color`.
+pub struct RasterDrawer {
+    display_list: DisplayListDrawer<BitmapFont>,
+    image: RgbaImage,
+    style: Box<dyn Fn(&Widget) -> BoxStyle>,
+    pub background: Rgba,
+    pub text_color: Rgba,
+    pub image_color: Rgba,
+}
+impl RasterDrawer {
+    /// Drawer for a window of `width` x `height` pixels
+    pub fn new(width: u32, height: u32, font: BitmapFont) -> RasterDrawer {
+        let background = [0xFF, 0xFF, 0xFF, 0xFF];
+        RasterDrawer {
+            display_list: DisplayListDrawer::new(font, Vec2::new(width as f32, height as f32)),
+            image: RgbaImage::new(width, height, background),
+            style: Box::new(default_box_style),
+            background,
+            text_color: [0x00, 0x00, 0x00, 0xFF],
+            image_color: [0x80, 0x80, 0xFF, 0xFF],
+        }
+    }
+    /// Decide how to draw the rectangle of every widget
+    pub fn with_style<F: Fn(&Widget) -> BoxStyle + 'static>(mut self, style: F) -> Self {
+        self.style = Box::new(style);
+        self
+    }
+    /// The image drawn in the last update
+    pub fn image(&self) -> &RgbaImage {
+        &self.image
+    }
+    fn render(&mut self, gui: &Gui<Self>) {
+        self.display_list.update_commands(&gui.root);
+        let scale = self.display_list.text_calc_mut().scale;
+        let (width, height) = (self.image.width(), self.image.height());
+        let window = Rect::new(Vec2::zero(), Vec2::new(width as f32, height as f32));
+
+        let mut image = RgbaImage::new(width, height, self.background);
+        let mut clip = window;
+        for command in self.display_list.commands() {
+            match command {
+                DrawCommand::Rect { id, rect } => {
+                    let style = (self.style)(gui.get(*id));
+                    if let Some(fill) = style.fill {
+                        image.fill_rect(*rect, clip, fill);
+                    }
+                    if let Some(border) = style.border {
+                        image.stroke_rect(*rect, clip, border);
+                    }
+                }
+                DrawCommand::Text { pos, text, .. } => {
+                    let scale = scale as f32;
+                    for (i, c) in text.chars().enumerate() {
+                        let origin = *pos + Vec2::new((i as u32 * GLYPH_WIDTH) as f32 * scale, 0.0);
+                        for x in 0..GLYPH_WIDTH {
+                            for y in 0..GLYPH_HEIGHT {
+                                if BitmapFont::pixel(c, x, y) {
+                                    let min = origin + Vec2::new(x as f32, y as f32) * scale;
+                                    let pixel = Rect::new(min, min + Vec2::new(scale, scale));
+                                    image.fill_rect(pixel, clip, self.text_color);
+                                }
+                            }
+                        }
+                    }
+                }
+                DrawCommand::Image { rect, .. } => image.fill_rect(*rect, clip, self.image_color),
+                DrawCommand::PushClip(rect) => clip = rect.intersection(window),
+                DrawCommand::PopClip => clip = window,
+            }
+        }
+        self.image = image;
+    }
+}
+impl GuiDrawer for RasterDrawer {
+    type Context = ();
+    type Calculator = BitmapFont;
+    fn window_size(&self, ctx: &mut Self::Context) -> Vec2 {
+        self.display_list.window_size(ctx)
+    }
+    fn transform_mouse(&self, m: Vec2, _ctx: &mut Self::Context) -> Vec2 {
+        m
+    }
+    fn update(
+        &mut self,
+        gui: &Gui<Self>,
+        _events: &[Event],
+        _log: Logger,
+        _ctx: &mut Self::Context,
+    ) -> Vec<WidgetOp> {
+        self.render(gui);
+        Vec::new()
+    }
+    fn text_calc(&mut self, layer: u32, ctx: &mut Self::Context) -> Self::Calculator {
+        self.display_list.text_calc(layer, ctx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_common::*;
+    #[test]
+    fn test_raster() {
+        let mut gui = TestGui::with_drawer(RasterDrawer::new(120, 60, BitmapFont::new(1)), ());
+        let button = gui.insert_in_root(Button::new());
+        gui.access(button)
+            .chain(Button::text_field)
+            .chain(TextField::text)
+            .put("Ok".to_string());
+        let text = gui.insert_in_root(TextField::new("Hi!".to_string()));
+        let image = gui.insert_in_root(Image::new("icon.png".to_string()).with_size(8.0, 8.0));
+        gui.update();
+        gui.update();
+        let drawer = gui.drawer();
+        let pixel = |id: Id, offset: Vec2| {
+            let pos = gui.get(id).pos + offset;
+            drawer.image().get_pixel(pos.x as u32, pos.y as u32)
+        };
+
+        // The button is filled and has a border
+        let style = default_box_style(gui.get(button));
+        assert_eq!(Some(pixel(button, Vec2::zero())), style.border);
+        assert_eq!(Some(pixel(button, Vec2::new(2.0, 2.0))), style.fill);
+        // The first column of '!' is empty, the third has a dot at the bottom
+        let text_width = gui.get(text).size.x;
+        assert_eq!(text_width, 3.0 * GLYPH_WIDTH as f32);
+        let exclamation = 2.0 * GLYPH_WIDTH as f32;
+        assert_eq!(pixel(text, Vec2::new(exclamation, 6.0)), drawer.background);
+        assert_eq!(
+            pixel(text, Vec2::new(exclamation + 2.0, 6.0)),
+            drawer.text_color
+        );
+        assert_eq!(
+            pixel(text, Vec2::new(exclamation + 2.0, 5.0)),
+            drawer.background
+        );
+        assert_eq!(pixel(image, Vec2::new(4.0, 4.0)), drawer.image_color);
+
+        assert_golden_image(
+            drawer.image(),
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/raster.png"),
+        );
+    }
+}
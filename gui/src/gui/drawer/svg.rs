@@ -0,0 +1,230 @@
+use crate::*;
+use slog::Logger;
+use std::fmt::Write;
+
+/// Renders the widget tree to an SVG document in every update, without any window (like
+/// `NoDrawer`), for example to produce snapshots of the GUI in CI.
+///
+/// Every widget becomes a `<rect>` (with `data-id` and `data-layer` attributes), text becomes
+/// `<text>` elements and images `<image>` elements (see `DisplayListDrawer`). Widgets that have an
+/// alias (see `Gui::aliases`) get it as `id` of their `<rect>`, made a valid XML id by `xml_id`.
+/// The appearance is given by a `<style>` element, which can be replaced with `with_css`.
+pub struct SvgDrawer<C> {
+    display_list: DisplayListDrawer<C>,
+    css: String,
+    svg: String,
+}
+impl<C: TextCalculator + Clone> SvgDrawer<C> {
+    /// `text_calc` should measure text like the font of the `<text>` elements
+    pub fn new(text_calc: C, window_size: Vec2) -> Self {
+        SvgDrawer {
+            display_list: DisplayListDrawer::new(text_calc, window_size),
+            css: "rect { fill: none; stroke: #808080; } text { font-family: monospace; }"
+                .to_string(),
+            svg: String::new(),
+        }
+    }
+    /// Replace the style sheet of the document
+    pub fn with_css(mut self, css: String) -> Self {
+        self.css = css;
+        self
+    }
+    pub fn set_window_size(&mut self, window_size: Vec2) {
+        self.display_list.set_window_size(window_size);
+    }
+    /// The document produced by the last update
+    pub fn svg(&self) -> &str {
+        &self.svg
+    }
+    fn render(&mut self, gui: &Gui<Self>) -> Result<String, std::fmt::Error> {
+        self.display_list.update_commands(&gui.root);
+        let size = GuiDrawer::window_size(&self.display_list, &mut ());
+        let font_size = self.display_list.text_calc_mut().line_height();
+
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = size.x,
+            h = size.y
+        )?;
+        writeln!(svg, "<style>{}</style>", escape(&self.css))?;
+        let mut clips = 0;
+        for command in self.display_list.commands() {
+            match command {
+                DrawCommand::Rect { id, rect } => {
+                    write!(svg, "<rect")?;
+                    if let Some(alias) = gui.aliases.get_by_right(id) {
+                        write!(svg, r#" id="{}""#, xml_id(alias))?;
+                    }
+                    writeln!(
+                        svg,
+                        r#" x="{}" y="{}" width="{}" height="{}" data-id="{}" data-layer="{}"/>"#,
+                        rect.min.x,
+                        rect.min.y,
+                        rect.size().x,
+                        rect.size().y,
+                        id,
+                        gui.get(*id).layer
+                    )?;
+                }
+                DrawCommand::Text { id, pos, text } => writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" font-size="{}" dominant-baseline="hanging" xml:space="preserve" data-id="{}">{}</text>"#,
+                    pos.x,
+                    pos.y,
+                    font_size,
+                    id,
+                    escape(text)
+                )?,
+                DrawCommand::Image { id, rect, source } => writeln!(
+                    svg,
+                    r#"<image href="{}" x="{}" y="{}" width="{}" height="{}" preserveAspectRatio="none" data-id="{}"/>"#,
+                    escape(source),
+                    rect.min.x,
+                    rect.min.y,
+                    rect.size().x,
+                    rect.size().y,
+                    id
+                )?,
+                DrawCommand::PushClip(rect) => {
+                    clips += 1;
+                    writeln!(
+                        svg,
+                        r#"<clipPath id="gui-clip-{}"><rect x="{}" y="{}" width="{}" height="{}"/></clipPath>"#,
+                        clips,
+                        rect.min.x,
+                        rect.min.y,
+                        rect.size().x,
+                        rect.size().y
+                    )?;
+                    writeln!(svg, r#"<g clip-path="url(#gui-clip-{})">"#, clips)?;
+                }
+                DrawCommand::PopClip => writeln!(svg, "</g>")?,
+            }
+        }
+        writeln!(svg, "</svg>")?;
+        Ok(svg)
+    }
+}
+impl<C: TextCalculator + Clone> GuiDrawer for SvgDrawer<C> {
+    type Context = ();
+    type Calculator = C;
+    fn window_size(&self, ctx: &mut Self::Context) -> Vec2 {
+        self.display_list.window_size(ctx)
+    }
+    fn transform_mouse(&self, m: Vec2, _ctx: &mut Self::Context) -> Vec2 {
+        m
+    }
+    fn update(
+        &mut self,
+        gui: &Gui<Self>,
+        _events: &[Event],
+        _log: Logger,
+        _ctx: &mut Self::Context,
+    ) -> Vec<WidgetOp> {
+        // (writing to a `String` can not fail)
+        self.svg = self.render(gui).unwrap();
+        Vec::new()
+    }
+    fn text_calc(&mut self, layer: u32, ctx: &mut Self::Context) -> Self::Calculator {
+        self.display_list.text_calc(layer, ctx)
+    }
+}
+
+/// The id of the `<rect>` of a widget with the alias `alias`: characters that can not be part of
+/// an XML id are replaced with `_`, and `_` is prepended if the alias does not start with a
+/// letter or `_`, or if it starts with `gui-`, which is reserved for the ids of generated
+/// elements (like `gui-clip-1`). So `"menu title"` becomes `menu_title` and `"2nd"` becomes
+/// `_2nd`.
+pub fn xml_id(alias: &str) -> String {
+    let mut id: String = alias
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let valid_start = id
+        .chars()
+        .next()
+        .map_or(false, |c| c.is_alphabetic() || c == '_');
+    if !valid_start || id.starts_with("gui-") {
+        id.insert(0, '_');
+    }
+    id
+}
+
+/// Escape characters that have a special meaning in XML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_common::*;
+    #[test]
+    fn test_svg() {
+        let drawer = SvgDrawer::new(NoTextCalculator, Vec2::new(400.0, 300.0));
+        let mut gui = TestGui::with_drawer(drawer, ());
+        gui.insert_in_root_with_alias(
+            TextField::new("Fish & <chips>".to_string()),
+            "menu title".to_string(),
+        );
+        // (an alias that looks like a generated id)
+        let area = gui.insert_in_root(ScrollArea::new());
+        gui.aliases.insert("gui-clip-1".to_string(), area);
+        gui.access(area).configure(|config| {
+            config.set_size(100.0, 50.0);
+        });
+        let image = gui
+            .access(area)
+            .chain(ScrollArea::content)
+            .get_widget_mut()
+            .insert_child(Image::new("icon.png".to_string()).with_size(32.0, 16.0));
+        gui.update();
+        gui.update();
+        let svg = gui.drawer().svg();
+
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="400" height="300" viewBox="0 0 400 300">"#
+        ));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains(
+            r#"<rect id="menu_title" x="0" y="0" width="140" height="10" data-id="2" data-layer="0"/>"#
+        ));
+        assert!(svg.contains(">Fish &amp; &lt;chips&gt;</text>"));
+        assert!(svg.contains(r#"<rect id="_gui-clip-1" "#));
+        assert_eq!(svg.matches(r#"id="gui-clip-1""#).count(), 1);
+        let pos = gui.get(image).pos;
+        assert!(svg.contains(&format!(
+            r#"<image href="icon.png" x="{}" y="{}" width="32" height="16""#,
+            pos.x, pos.y
+        )));
+
+        // The image is inside the clipped group of the scroll area
+        let clip = svg.find(r#"<g clip-path="url(#gui-clip-1)">"#).unwrap();
+        let image = svg.find("<image").unwrap();
+        let end = svg.find("</g>").unwrap();
+        assert!(clip < image && image < end);
+    }
+
+    #[test]
+    fn test_xml_id() {
+        assert_eq!(xml_id("menu"), "menu");
+        assert_eq!(xml_id("menu title"), "menu_title");
+        assert_eq!(xml_id("a<\"&>.b-c"), "a____.b-c");
+        assert_eq!(xml_id("2nd"), "_2nd");
+        assert_eq!(xml_id("-x"), "_-x");
+        assert_eq!(xml_id(""), "_");
+        assert_eq!(xml_id("gui-clip-1"), "_gui-clip-1");
+        assert_eq!(xml_id("élan"), "élan");
+    }
+}
@@ -0,0 +1,251 @@
+use crate::*;
+use slog::Logger;
+use std::{io, io::Write, marker::PhantomData, ops::Range};
+
+/// Measures text in whole character cells of `cell_size` (in layout space), for `TuiDrawer`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CellTextCalculator {
+    pub cell_size: Vec2,
+}
+impl TextCalculator for CellTextCalculator {
+    fn text_size(&mut self, text: &str) -> Vec2 {
+        Vec2::new(
+            text.chars().count() as f32 * self.cell_size.x,
+            self.cell_size.y,
+        )
+    }
+    fn char_offsets(&mut self, text: &str) -> Vec<f32> {
+        (0..=text.chars().count())
+            .map(|i| i as f32 * self.cell_size.x)
+            .collect()
+    }
+}
+
+/// Draws the widget tree on a grid of character cells, and writes it with ANSI escape sequences
+/// to the context (any `Write`, such as `std::io::Stdout`) in every update.
+///
+/// Every cell covers `cell_size` of layout space, so the widgets keep their usual sizes.
+/// Mouse positions are expected in cells (column and row, from zero), and are transformed to
+/// the center of the cell (see `GuiDrawer::transform_mouse`).
+///
+/// Widgets that capture the mouse (such as buttons) are drawn as boxes, with a double line while
+/// hovered; text is written into the cells, and images are filled with `▒`.
+pub struct TuiDrawer<W> {
+    display_list: DisplayListDrawer<CellTextCalculator>,
+    cell_size: Vec2,
+    columns: u32,
+    rows: u32,
+    cells: Vec<Vec<char>>,
+    /// Error from writing in the last update
+    error: Option<io::Error>,
+    _writer: PhantomData<W>,
+}
+impl<W: Write> TuiDrawer<W> {
+    /// Drawer for a terminal of `columns` x `rows` cells of 10 x 10 units of layout space
+    pub fn new(columns: u32, rows: u32) -> TuiDrawer<W> {
+        TuiDrawer::with_cell_size(columns, rows, Vec2::new(10.0, 10.0))
+    }
+    pub fn with_cell_size(columns: u32, rows: u32, cell_size: Vec2) -> TuiDrawer<W> {
+        TuiDrawer {
+            display_list: DisplayListDrawer::new(
+                CellTextCalculator { cell_size },
+                Vec2::new(columns as f32 * cell_size.x, rows as f32 * cell_size.y),
+            ),
+            cell_size,
+            columns,
+            rows,
+            cells: vec![vec![' '; columns as usize]; rows as usize],
+            error: None,
+            _writer: PhantomData,
+        }
+    }
+    /// Resize the grid, for example when the terminal is resized
+    pub fn set_size(&mut self, columns: u32, rows: u32) {
+        let cell_size = self.cell_size;
+        self.columns = columns;
+        self.rows = rows;
+        self.display_list.set_window_size(Vec2::new(
+            columns as f32 * cell_size.x,
+            rows as f32 * cell_size.y,
+        ));
+    }
+    pub fn cell_size(&self) -> Vec2 {
+        self.cell_size
+    }
+    /// The rows of cells drawn in the last update
+    pub fn lines(&self) -> Vec<String> {
+        self.cells.iter().map(|row| row.iter().collect()).collect()
+    }
+    /// Error from writing to the context in the last update, if any
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+    fn render(&mut self, gui: &Gui<Self>) {
+        self.display_list.update_commands(&gui.root);
+        let cell_size = self.cell_size;
+        let grid = Cells {
+            columns: 0..self.columns,
+            rows: 0..self.rows,
+        };
+        let mut cells = vec![vec![' '; self.columns as usize]; self.rows as usize];
+        let mut clip = grid.clone();
+        for command in self.display_list.commands() {
+            match command {
+                DrawCommand::Rect { id, rect } => {
+                    let w = gui.get(*id);
+                    if !w.captures().mouse {
+                        continue;
+                    }
+                    let Cells { columns, rows } = Cells::around(*rect, cell_size);
+                    let (last_column, last_row) = (columns.end - 1, rows.end - 1);
+                    let lines = if w.hover() {
+                        ['╔', '╗', '╚', '╝', '═', '║']
+                    } else {
+                        ['┌', '┐', '└', '┘', '─', '│']
+                    };
+                    for row in rows.clone() {
+                        for column in columns.clone() {
+                            let c = match (column, row) {
+                                (x, y) if x == columns.start && y == rows.start => lines[0],
+                                (x, y) if x == last_column && y == rows.start => lines[1],
+                                (x, y) if x == columns.start && y == last_row => lines[2],
+                                (x, y) if x == last_column && y == last_row => lines[3],
+                                (_, y) if y == rows.start || y == last_row => lines[4],
+                                (x, _) if x == columns.start || x == last_column => lines[5],
+                                _ => ' ',
+                            };
+                            clip.put(&mut cells, column, row, c);
+                        }
+                    }
+                }
+                DrawCommand::Text { pos, text, .. } => {
+                    let column = (pos.x / cell_size.x).round();
+                    let row = (pos.y / cell_size.y).round();
+                    if column < 0.0 || row < 0.0 {
+                        continue;
+                    }
+                    for (i, c) in text.chars().enumerate() {
+                        clip.put(&mut cells, column as u32 + i as u32, row as u32, c);
+                    }
+                }
+                DrawCommand::Image { rect, .. } => {
+                    let Cells { columns, rows } = Cells::within(*rect, cell_size);
+                    for row in rows {
+                        for column in columns.clone() {
+                            clip.put(&mut cells, column, row, '▒');
+                        }
+                    }
+                }
+                DrawCommand::PushClip(rect) => clip = Cells::within(*rect, cell_size),
+                DrawCommand::PopClip => clip = grid.clone(),
+            }
+        }
+        self.cells = cells;
+    }
+    fn write(&self, out: &mut W) -> io::Result<()> {
+        for (i, row) in self.cells.iter().enumerate() {
+            // Move the cursor to the start of the row
+            write!(out, "\x1b[{};1H", i + 1)?;
+            write!(out, "{}", row.iter().collect::<String>())?;
+        }
+        out.flush()
+    }
+}
+impl<W: Write> GuiDrawer for TuiDrawer<W> {
+    type Context = W;
+    type Calculator = CellTextCalculator;
+    fn window_size(&self, _ctx: &mut Self::Context) -> Vec2 {
+        self.display_list.window_size(&mut ())
+    }
+    fn transform_mouse(&self, m: Vec2, _ctx: &mut Self::Context) -> Vec2 {
+        let cell_size = self.cell_size;
+        Vec2::new((m.x + 0.5) * cell_size.x, (m.y + 0.5) * cell_size.y)
+    }
+    fn update(
+        &mut self,
+        gui: &Gui<Self>,
+        _events: &[Event],
+        _log: Logger,
+        ctx: &mut Self::Context,
+    ) -> Vec<WidgetOp> {
+        self.render(gui);
+        self.error = self.write(ctx).err();
+        Vec::new()
+    }
+    fn text_calc(&mut self, layer: u32, _ctx: &mut Self::Context) -> Self::Calculator {
+        self.display_list.text_calc(layer, &mut ())
+    }
+}
+
+/// A rectangle of cells
+#[derive(Clone, Debug)]
+struct Cells {
+    columns: Range<u32>,
+    rows: Range<u32>,
+}
+impl Cells {
+    /// The cells that `rect` touches
+    fn around(rect: Rect, cell_size: Vec2) -> Cells {
+        let range = |min: f32, max: f32, cell: f32| {
+            let start = (min / cell).floor().max(0.0) as u32;
+            start..((max / cell).ceil().max(0.0) as u32).max(start + 1)
+        };
+        Cells {
+            columns: range(rect.min.x, rect.max.x, cell_size.x),
+            rows: range(rect.min.y, rect.max.y, cell_size.y),
+        }
+    }
+    /// The cells whose centers are inside `rect`
+    fn within(rect: Rect, cell_size: Vec2) -> Cells {
+        let range = |min: f32, max: f32, cell: f32| {
+            let bound = |v: f32| (v / cell - 0.5).ceil().max(0.0) as u32;
+            bound(min)..bound(max)
+        };
+        Cells {
+            columns: range(rect.min.x, rect.max.x, cell_size.x),
+            rows: range(rect.min.y, rect.max.y, cell_size.y),
+        }
+    }
+    /// Set the cell at `(column, row)` to `c`, if it is inside `self` and `cells`
+    fn put(&self, cells: &mut [Vec<char>], column: u32, row: u32, c: char) {
+        if self.columns.contains(&column) && self.rows.contains(&row) {
+            if let Some(cell) = cells
+                .get_mut(row as usize)
+                .and_then(|cells| cells.get_mut(column as usize))
+            {
+                *cell = c;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_common::*;
+    #[test]
+    fn test_tui() {
+        let mut gui = TestGui::with_drawer(TuiDrawer::new(8, 4), Vec::new());
+        let button = gui.insert_in_root(Button::new());
+        gui.access(button)
+            .chain(Button::text_field)
+            .chain(TextField::text)
+            .put("Ok".to_string());
+        gui.move_mouse(Vec2::new(7.0, 3.0));
+        gui.ctx.clear();
+        gui.update();
+        assert_eq!(
+            gui.drawer().lines(),
+            vec!["┌──┐    ", "│Ok│    ", "└──┘    ", "        "]
+        );
+        let out = String::from_utf8(std::mem::take(&mut gui.ctx)).unwrap();
+        assert!(out.starts_with("\x1b[1;1H┌──┐    \x1b[2;1H│Ok│    "));
+        assert!(out.ends_with("\x1b[4;1H        "));
+
+        // The mouse is in cells
+        gui.move_mouse(Vec2::new(1.0, 1.0));
+        gui.update();
+        assert_eq!(gui.drawer().lines()[0], "╔══╗    ");
+        assert!(gui.drawer_mut().take_error().is_none());
+    }
+}
@@ -2,7 +2,13 @@ use crate::*;
 use bimap::BiMap;
 use indexmap::IndexMap;
 use slog::Logger;
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+use winit::event::{ModifiersState, MouseButton, VirtualKeyCode};
 
 mod drawer;
 pub use drawer::*;
@@ -18,6 +24,52 @@ pub type GuiShared = Rc<RefCell<GuiInternal>>;
 
 pub const ROOT: usize = 1;
 
+/// Keys for which `EventKind::KeyPress` and `EventKind::KeyRelease` are sent to the focused widget:
+/// every `VirtualKeyCode`. `Input` can only be asked about one key at a time, so all of them are
+/// checked in every update.
+#[rustfmt::skip]
+const KEYS: &[VirtualKeyCode] = {
+    use VirtualKeyCode::*;
+    &[
+        Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9, Key0,
+        A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+        Escape, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+        F13, F14, F15, F16, F17, F18, F19, F20, F21, F22, F23, F24,
+        Snapshot, Scroll, Pause, Insert, Home, Delete, End, PageDown, PageUp,
+        Left, Up, Right, Down, Back, Return, Space, Compose, Caret,
+        Numlock, Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7, Numpad8,
+        Numpad9, AbntC1, AbntC2, Add, Apostrophe, Apps, At, Ax, Backslash, Calculator, Capital,
+        Colon, Comma, Convert, Decimal, Divide, Equals, Grave, Kana, Kanji,
+        LAlt, LBracket, LControl, LShift, LWin, Mail, MediaSelect, MediaStop, Minus, Multiply, Mute,
+        MyComputer, NavigateForward, NavigateBackward, NextTrack, NoConvert,
+        NumpadComma, NumpadEnter, NumpadEquals, OEM102, Period, PlayPause, Power, PrevTrack,
+        RAlt, RBracket, RControl, RShift, RWin, Semicolon, Slash, Sleep, Stop, Subtract, Sysrq,
+        Tab, Underline, Unlabeled, VolumeDown, VolumeUp, Wake, WebBack, WebFavorites, WebForward,
+        WebHome, WebRefresh, WebSearch, WebStop, Yen, Copy, Paste, Cut,
+    ]
+};
+
+/// Mouse buttons for which `EventKind::Press` and `EventKind::Release` are sent.
+/// Extra buttons are numbered differently depending on the platform.
+const MOUSE_BUTTONS: &[MouseButton] = {
+    use MouseButton::*;
+    &[
+        Left,
+        Right,
+        Middle,
+        Other(0),
+        Other(1),
+        Other(2),
+        Other(3),
+        Other(4),
+        Other(5),
+        Other(6),
+        Other(7),
+        Other(8),
+        Other(9),
+    ]
+};
+
 pub trait AsId<D: GuiDrawer>: Clone + std::fmt::Display {
     fn resolve(&self, gui: &Gui<D>) -> Option<Id>;
 }
@@ -51,7 +103,38 @@ pub struct GuiInternal {
     to_remove: Vec<Id>,
     /// Events collected outside update function, consumed when update is called.
     events: Vec<Event>,
-    pub text_calc: Box<dyn TextCalculator>,
+    /// Indices into `events` of the events that a widget has consumed (see
+    /// `WidgetContext::consume`)
+    consumed: HashSet<usize>,
+    /// The text calculator of each layer (see `GuiDrawer::text_calc`), indexed by layer
+    text_calcs: Vec<Box<dyn TextCalculator>>,
+
+    /// Maximum time between two clicks of a double-click
+    double_click_time: Duration,
+    /// Maximum distance between two clicks of a double-click
+    double_click_distance: f32,
+
+    // Input state of the current frame, for widgets that need more than events
+    /// Time of the current frame (see `Gui::set_time`)
+    now: Instant,
+    mouse_pos: Vec2,
+    mouse_delta: Vec2,
+    mouse_down: bool,
+    scroll_delta: Vec2,
+    modifiers: ModifiersState,
+    button_presses: Vec<MouseButton>,
+    button_releases: Vec<MouseButton>,
+    key_presses: Vec<VirtualKeyCode>,
+    key_releases: Vec<VirtualKeyCode>,
+    chars: Vec<char>,
+
+    /// The widget that receives keyboard input
+    focused: Option<Id>,
+    /// True during the update of a frame where the mouse was pressed, until a focusable widget
+    /// has taken focus
+    click_focus_pending: bool,
+    /// The widget that receives mouse movement and release while the mouse button is held
+    pointer_capture: Option<Id>,
 }
 impl GuiInternal {
     pub fn new<T: TextCalculator>(text_calc: T) -> Self {
@@ -60,16 +143,179 @@ impl GuiInternal {
             id_cnt: ROOT,
             to_remove: Vec::new(),
             events: Vec::new(),
-            text_calc: Box::new(text_calc),
+            consumed: HashSet::new(),
+            text_calcs: vec![Box::new(text_calc)],
+
+            double_click_time: Duration::from_millis(500),
+            double_click_distance: 4.0,
+
+            now: Instant::now(),
+            mouse_pos: Vec2::zero(),
+            mouse_delta: Vec2::zero(),
+            mouse_down: false,
+            scroll_delta: Vec2::zero(),
+            modifiers: ModifiersState::default(),
+            button_presses: Vec::new(),
+            button_releases: Vec::new(),
+            key_presses: Vec::new(),
+            key_releases: Vec::new(),
+            chars: Vec::new(),
+
+            focused: None,
+            click_focus_pending: false,
+            pointer_capture: None,
         }
     }
 
+    /// The text calculator for widgets on `layer`. Falls back to the one of layer 0 until `Gui`
+    /// has taken one from the drawer for `layer`.
+    pub fn text_calc(&mut self, layer: u32) -> &mut dyn TextCalculator {
+        let layer = if (layer as usize) < self.text_calcs.len() {
+            layer as usize
+        } else {
+            0
+        };
+        &mut *self.text_calcs[layer]
+    }
+
     pub fn push_event(&mut self, event: Event) {
         self.events.push(event);
     }
     pub fn events(&self) -> &[Event] {
         &self.events
     }
+    /// Mark the first event from index `start` on that equals `event` and is not consumed yet as
+    /// consumed
+    pub(crate) fn consume_event(&mut self, start: usize, event: &Event) {
+        let consumed = &self.consumed;
+        let idx = (start..self.events.len())
+            .find(|idx| self.events[*idx] == *event && !consumed.contains(idx));
+        if let Some(idx) = idx {
+            self.consumed.insert(idx);
+        }
+    }
+    pub(crate) fn is_consumed(&self, idx: usize) -> bool {
+        self.consumed.contains(&idx)
+    }
+    /// Mouse position in the current frame, in the coordinate system of the widgets
+    pub fn mouse_pos(&self) -> Vec2 {
+        self.mouse_pos
+    }
+    /// Two clicks on a widget with the same button make a double-click if the second click is
+    /// at most `time` after the first, and at most `distance` away from it
+    pub fn set_double_click_threshold(&mut self, time: Duration, distance: f32) {
+        self.double_click_time = time;
+        self.double_click_distance = distance;
+    }
+    pub fn double_click_time(&self) -> Duration {
+        self.double_click_time
+    }
+    pub fn double_click_distance(&self) -> f32 {
+        self.double_click_distance
+    }
+    /// Time of the current frame, for timing such as double-clicks (see `Gui::set_time`)
+    pub fn now(&self) -> Instant {
+        self.now
+    }
+    /// Mouse buttons pressed in the current frame
+    pub fn button_presses(&self) -> &[MouseButton] {
+        &self.button_presses
+    }
+    /// Mouse buttons released in the current frame
+    pub fn button_releases(&self) -> &[MouseButton] {
+        &self.button_releases
+    }
+    /// Mouse movement since the previous frame
+    pub fn mouse_delta(&self) -> Vec2 {
+        self.mouse_delta
+    }
+    /// Whether the left mouse button is held down in the current frame
+    pub fn mouse_down(&self) -> bool {
+        self.mouse_down
+    }
+    /// Mouse wheel movement in the current frame
+    pub fn scroll_delta(&self) -> Vec2 {
+        self.scroll_delta
+    }
+    /// Should be called by a widget that handles `EventKind::Scroll`, so that the scroll does not
+    /// bubble further up to its ancestors, and so that `Gui::update` captures the mouse.
+    /// Consumes the last `EventKind::Scroll` event, which is not necessarily the one that the
+    /// widget handles.
+    #[deprecated(note = "use `WidgetContext::consume` with the `EventKind::Scroll` event")]
+    pub fn consume_scroll(&mut self) {
+        let last = self
+            .events
+            .iter()
+            .rposition(|event| matches!(event.kind, EventKind::Scroll { .. }));
+        if let Some(idx) = last {
+            self.consumed.insert(idx);
+        }
+    }
+    /// Whether a widget has handled the mouse wheel in the current frame
+    pub fn scroll_consumed(&self) -> bool {
+        self.consumed
+            .iter()
+            .any(|idx| matches!(self.events[*idx].kind, EventKind::Scroll { .. }))
+    }
+    /// Modifier keys held down in the current frame
+    pub fn modifiers(&self) -> ModifiersState {
+        self.modifiers
+    }
+    /// The widget that currently has keyboard focus
+    pub fn focused(&self) -> Option<Id> {
+        self.focused
+    }
+    /// Give keyboard focus to `id`, or remove focus altogether if `None`.
+    /// Emits `EventKind::Unfocus` for the previously focused widget and `EventKind::Focus` for the
+    /// new one.
+    pub fn set_focus(&mut self, id: Option<Id>) {
+        if id == self.focused {
+            return;
+        }
+        if let Some(prev) = self.focused {
+            self.push_event(Event::new(prev, EventKind::Unfocus));
+        }
+        if let Some(id) = id {
+            self.push_event(Event::new(id, EventKind::Focus));
+        }
+        self.focused = id;
+    }
+    /// Make `id` receive `EventKind::Drag` and `EventKind::Release` until the mouse button is
+    /// released, even when the mouse leaves it. Meanwhile, no other widget is hovered.
+    /// Should be called by a widget that just received `EventKind::Press`; has no effect if the
+    /// mouse button is not held.
+    pub fn capture_pointer(&mut self, id: Id) {
+        if self.mouse_down {
+            self.pointer_capture = Some(id);
+        }
+    }
+    /// The widget that has captured the pointer, if any
+    pub fn pointer_capture(&self) -> Option<Id> {
+        self.pointer_capture
+    }
+    /// Called when a focusable widget is pressed. Only the first (that is, the deepest) widget
+    /// to claim focus in a frame gets it.
+    pub(crate) fn claim_click_focus(&mut self, id: Id) {
+        if self.click_focus_pending {
+            self.click_focus_pending = false;
+            self.set_focus(Some(id));
+        }
+    }
+    /// Events of keys pressed and released and characters typed in the current frame, to be sent
+    /// to the focused widget
+    pub(crate) fn keyboard_events(&self) -> Vec<EventKind> {
+        let modifiers = self.modifiers;
+        self.key_presses
+            .iter()
+            .map(|&key| EventKind::KeyPress { key, modifiers })
+            .chain(
+                self.key_releases
+                    .iter()
+                    .map(|&key| EventKind::KeyRelease { key, modifiers }),
+            )
+            .chain(self.chars.iter().map(|&c| EventKind::Char(c)))
+            .collect()
+    }
     pub fn remove(&mut self, id: Id) {
         self.to_remove.push(id);
     }
@@ -93,6 +339,12 @@ pub struct Gui<D> {
     drawer: Option<D>,
     pub aliases: BiMap<String, Id>,
     pub internal: Rc<RefCell<GuiInternal>>,
+    /// Events of the last update that were consumed by a widget
+    consumed_events: Vec<Event>,
+    /// Whether the drawer returned `WidgetOp::RequestRedraw` in the last update
+    redraw_requested: bool,
+    /// Time of the frames, instead of the system clock (see `Gui::set_time`)
+    time: Option<Instant>,
 }
 
 impl<D: GuiDrawer> Gui<D> {
@@ -106,6 +358,9 @@ impl<D: GuiDrawer> Gui<D> {
             screen: (0.0, 0.0),
             internal,
             aliases: BiMap::new(),
+            consumed_events: Vec::new(),
+            redraw_requested: false,
+            time: None,
         }
     }
     pub fn shared(&self) -> GuiShared {
@@ -114,6 +369,12 @@ impl<D: GuiDrawer> Gui<D> {
     pub fn shared_ref(&self) -> &GuiShared {
         &self.internal
     }
+    pub fn drawer(&self) -> &D {
+        self.drawer.as_ref().unwrap()
+    }
+    pub fn drawer_mut(&mut self) -> &mut D {
+        self.drawer.as_mut().unwrap()
+    }
     /// Constructs a [`LensDriver`] to access a widget given by `id`
     pub fn access<I: AsId<D>>(&mut self, id: I) -> LensRoot {
         let internal = self.internal.clone();
@@ -255,23 +516,133 @@ impl<D: GuiDrawer> Gui<D> {
         let Vec2 { x: sw, y: sh } = self.drawer.as_mut().unwrap().window_size(ctx);
         self.root.config.set_size(sw, sh);
 
+        {
+            let mut internal = self.internal.borrow_mut();
+            internal.mouse_delta = mouse - internal.mouse_pos;
+            internal.mouse_pos = mouse;
+            if input.is_mouse_button_toggled_down(MouseButton::Left) {
+                internal.mouse_down = true;
+            } else if input.is_mouse_button_toggled_up(MouseButton::Left) {
+                internal.mouse_down = false;
+            }
+            internal.button_presses = MOUSE_BUTTONS
+                .iter()
+                .cloned()
+                .filter(|button| input.is_mouse_button_toggled_down(*button))
+                .collect();
+            internal.button_releases = MOUSE_BUTTONS
+                .iter()
+                .cloned()
+                .filter(|button| input.is_mouse_button_toggled_up(*button))
+                .collect();
+            internal.scroll_delta = input.get_mouse_wheel().into();
+            internal.now = self.time.unwrap_or_else(Instant::now);
+
+            let mut modifiers = ModifiersState::empty();
+            let down = |keys: &[VirtualKeyCode]| keys.iter().any(|key| input.is_key_down(*key));
+            modifiers.set(
+                ModifiersState::SHIFT,
+                down(&[VirtualKeyCode::LShift, VirtualKeyCode::RShift]),
+            );
+            modifiers.set(
+                ModifiersState::CTRL,
+                down(&[VirtualKeyCode::LControl, VirtualKeyCode::RControl]),
+            );
+            modifiers.set(
+                ModifiersState::ALT,
+                down(&[VirtualKeyCode::LAlt, VirtualKeyCode::RAlt]),
+            );
+            modifiers.set(
+                ModifiersState::LOGO,
+                down(&[VirtualKeyCode::LWin, VirtualKeyCode::RWin]),
+            );
+            internal.modifiers = modifiers;
+            internal.key_presses = KEYS
+                .iter()
+                .cloned()
+                .filter(|key| input.is_key_toggled_down(*key))
+                .collect();
+            internal.key_releases = KEYS
+                .iter()
+                .cloned()
+                .filter(|key| input.is_key_toggled_up(*key))
+                .collect();
+            // Control characters are covered by key events
+            internal.chars = input
+                .get_chars()
+                .iter()
+                .cloned()
+                .filter(|c| !c.is_control())
+                .collect();
+            internal.click_focus_pending = input.is_mouse_button_toggled_down(MouseButton::Left);
+        }
+
         // Delete widgets that were marked for deletion last frame
         {
             let to_remove =
                 std::mem::replace(&mut self.internal.borrow_mut().to_remove, Vec::new());
             for id_to_remove in to_remove {
-                let parent_id = self.parent(id_to_remove);
+                // A removed widget can not keep the focus or pointer capture
+                let mut internal = self.internal.borrow_mut();
+                let removed = |internal: &GuiInternal, id: Option<Id>| {
+                    id.map(|id| id == id_to_remove || internal.get_path(id).contains(&id_to_remove))
+                        .unwrap_or(false)
+                };
+                if removed(&internal, internal.focused) {
+                    internal.focused = None;
+                }
+                if removed(&internal, internal.pointer_capture) {
+                    internal.pointer_capture = None;
+                }
+                drop(internal);
+                let parent_id = self.try_parent(id_to_remove).unwrap_or(ROOT);
                 let parent = self.get_mut(parent_id);
                 parent.remove(id_to_remove);
             }
         }
 
         // 3 traversals
-        let capture = self
+        let mut capture = self
             .root
             .update_bottom_up(input, sw, sh, mouse, log.clone());
+        {
+            let mut internal = self.internal.borrow_mut();
+            if internal.pointer_capture.is_some() {
+                capture.mouse = true;
+                if !internal.mouse_down {
+                    internal.pointer_capture = None;
+                }
+            }
+            if internal.scroll_consumed() {
+                capture.mouse = true;
+            }
+            // Pressing outside any focusable widget removes focus
+            if internal.click_focus_pending {
+                internal.click_focus_pending = false;
+                internal.set_focus(None);
+            }
+        }
+        // Tab / Shift-Tab moves focus
+        let tab = {
+            let internal = self.internal.borrow();
+            if internal.key_presses.contains(&VirtualKeyCode::Tab) {
+                Some(internal.modifiers.shift())
+            } else {
+                None
+            }
+        };
+        match tab {
+            Some(false) => self.focus_next(),
+            Some(true) => self.focus_previous(),
+            None => (),
+        }
+        let focused = self.internal.borrow().focused;
+        capture.keyboard = focused
+            .and_then(|id| self.try_get(id))
+            .map(|w| w.captures().keyboard)
+            .unwrap_or(false);
+        self.update_text_calcs(ctx);
         self.root.layout_alg();
-        self.root.update_top_down();
 
         // Update parent relations
         {
@@ -280,6 +651,9 @@ impl<D: GuiDrawer> Gui<D> {
             update_paths_recurse(vec![], &mut self.root, &mut internal.paths);
         }
 
+        self.resolve_relative_placements();
+        self.root.update_top_down();
+
         // Emit Remove events (without removing widgets)
         {
             // (move `to_remove` out and back in again)
@@ -302,16 +676,195 @@ impl<D: GuiDrawer> Gui<D> {
             std::mem::replace(&mut self.internal.borrow_mut().to_remove, to_remove);
         }
 
-        let events = std::mem::replace(&mut self.internal.borrow_mut().events, Vec::new());
+        let mut events = std::mem::replace(&mut self.internal.borrow_mut().events, Vec::new());
+        let consumed = std::mem::replace(&mut self.internal.borrow_mut().consumed, HashSet::new());
+        let mut consumed = consumed.into_iter().collect::<Vec<_>>();
+        consumed.sort();
+        self.consumed_events = consumed.iter().map(|idx| events[*idx].clone()).collect();
 
         let mut drawer = self.drawer.take().unwrap();
         let ops = drawer.update(self, &events, log, ctx);
         self.drawer = Some(drawer);
+        events.extend(self.apply_ops(ops, ctx));
+
+        (events, capture)
+    }
+
+    /// Takes text calculators from the drawer for the layers that do not have one yet.
+    fn update_text_calcs(&mut self, ctx: &mut D::Context) {
+        let max_layer = self
+            .root
+            .recursive_children_iter()
+            .map(|widget| widget.layer)
+            .max()
+            .unwrap_or(0);
+        let mut layer = self.internal.borrow().text_calcs.len() as u32;
+        while layer <= max_layer {
+            let text_calc = self.drawer_mut().text_calc(layer, ctx);
+            self.internal
+                .borrow_mut()
+                .text_calcs
+                .push(Box::new(text_calc));
+            layer += 1;
+        }
+    }
+
+    /// Applies the operations returned by `GuiDrawer::update`, and returns the events that they
+    /// generated. Operations on widgets that do not exist are ignored.
+    fn apply_ops(&mut self, ops: Vec<WidgetOp>, ctx: &mut D::Context) -> Vec<Event> {
+        self.redraw_requested = false;
+        let mut relayout = false;
         for op in ops {
-            match op {}
+            match op {
+                WidgetOp::Remeasure(id) => {
+                    if let Some(layer) = self.try_get(id).map(|widget| widget.layer) {
+                        self.update_text_calcs(ctx);
+                        let text_calc = self.drawer_mut().text_calc(layer, ctx);
+                        self.internal.borrow_mut().text_calcs[layer as usize] = Box::new(text_calc);
+                        relayout = true;
+                    }
+                }
+                WidgetOp::SetIntrinsicSize(id, size) => {
+                    if let Some(widget) = self.try_get_mut(id) {
+                        widget.set_intrinsic_size(size);
+                        relayout = true;
+                    }
+                }
+                WidgetOp::RequestRedraw => self.redraw_requested = true,
+            }
+        }
+        if relayout {
+            self.root.layout_alg();
+            self.resolve_relative_placements();
+            self.root.update_top_down();
         }
+        std::mem::replace(&mut self.internal.borrow_mut().events, Vec::new())
+    }
 
-        (events, capture)
+    /// Use `time` as the time of the following updates instead of the system clock, for example to
+    /// test double-clicks independently of how fast the test runs. `None` returns to the system
+    /// clock.
+    pub fn set_time(&mut self, time: Option<Instant>) {
+        self.time = time;
+    }
+
+    /// Whether the drawer asked for another update in the next frame (see
+    /// `WidgetOp::RequestRedraw`), even if there is no input.
+    pub fn redraw_requested(&self) -> bool {
+        self.redraw_requested
+    }
+
+    /// The events returned by the last `Gui::update` that were consumed by a widget (see
+    /// `WidgetContext::consume`), in the order in which they were emitted
+    pub fn consumed_events(&self) -> &[Event] {
+        &self.consumed_events
+    }
+    /// Give keyboard focus to a widget. Events are emitted in the next update.
+    /// Returns false, and leaves the focus unchanged, if there is no such widget.
+    pub fn focus<I: AsId<D>>(&mut self, id: I) -> bool {
+        match id.resolve(self).filter(|id| self.try_get(*id).is_some()) {
+            Some(id) => {
+                self.internal.borrow_mut().set_focus(Some(id));
+                true
+            }
+            None => false,
+        }
+    }
+    /// Remove keyboard focus from any widget
+    pub fn unfocus(&mut self) {
+        self.internal.borrow_mut().set_focus(None);
+    }
+
+    /// Move focus to the next widget in tab order (see `Gui::tab_order`), wrapping around at the
+    /// end. If no widget is focused, the first one is focused.
+    pub fn focus_next(&mut self) {
+        self.focus_step(true)
+    }
+    /// Move focus to the previous widget in tab order (see `Gui::tab_order`), wrapping around at
+    /// the start. If no widget is focused, the last one is focused.
+    pub fn focus_previous(&mut self) {
+        self.focus_step(false)
+    }
+    fn focus_step(&mut self, forward: bool) {
+        let order = self.tab_order();
+        if order.is_empty() {
+            return;
+        }
+        let current = self
+            .internal
+            .borrow()
+            .focused
+            .and_then(|focused| order.iter().position(|id| *id == focused));
+        let next = match (current, forward) {
+            (Some(i), true) => (i + 1) % order.len(),
+            (Some(i), false) => (i + order.len() - 1) % order.len(),
+            (None, true) => 0,
+            (None, false) => order.len() - 1,
+        };
+        self.internal.borrow_mut().set_focus(Some(order[next]));
+    }
+    /// All focusable widgets in the order that Tab visits them.
+    /// Widgets with `WidgetConfig::tab_index` come first, sorted by tab index, followed by the
+    /// rest in tree order (pre-order, children in insertion order).
+    /// Widgets that are about to be removed are skipped.
+    pub fn tab_order(&self) -> Vec<Id> {
+        let to_remove = self.internal.borrow().to_remove.clone();
+        let mut order = Vec::new();
+        tab_order_recurse(&self.root, &to_remove, &mut order);
+        // (stable sort keeps tree order for equal tab indices)
+        order.sort_by_key(|(tab_index, _)| match tab_index {
+            Some(tab_index) => (0, *tab_index),
+            None => (1, 0),
+        });
+        order.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Position of a widget, calculated from the relative positions of all its ancestors.
+    /// Unlike `Widget::pos`, this is up to date after `Widget::layout_alg`.
+    fn absolute_pos(&self, id: Id) -> Vec2 {
+        let internal = self.internal.borrow();
+        let path = if id == ROOT {
+            &[][..]
+        } else {
+            internal.get_path(id)
+        };
+        path.iter()
+            .chain(std::iter::once(&id))
+            .map(|id| self.get(*id).rel_pos)
+            .fold(Vec2::zero(), |acc, rel_pos| acc + rel_pos)
+    }
+
+    /// Sets the relative positions of widgets placed with `PlacementAxis::Relative`, which depend on
+    /// the positions of other widgets in the tree.
+    fn resolve_relative_placements(&mut self) {
+        let ids = std::iter::once(&self.root)
+            .chain(self.root.recursive_children_iter())
+            .filter(|w| w.config.place.map(|p| p.is_relative()).unwrap_or(false))
+            .map(|w| w.get_id())
+            .collect::<Vec<_>>();
+        for id in ids {
+            let place = self.get(id).config.place.unwrap();
+            let size = self.get(id).size;
+            // (top-level widgets have no parent in `try_parent`)
+            let parent_pos = self.absolute_pos(self.try_parent(id).unwrap_or(ROOT));
+            let mut rel_pos = self.get(id).rel_pos;
+            for &axis in &[Axis::X, Axis::Y] {
+                if let (PlacementAxis::Relative { id: target, offset }, anchor) = place.axis(axis) {
+                    if self.try_get(target).is_none() {
+                        continue;
+                    }
+                    let target_pos = self.absolute_pos(target)[axis];
+                    let target_size = self.get(target).size[axis];
+                    let pos = match anchor {
+                        Anchor::Min => target_pos + offset,
+                        Anchor::Center => target_pos + (target_size - size[axis]) / 2.0 + offset,
+                        Anchor::Max => target_pos + target_size + offset,
+                    };
+                    rel_pos[axis] = pos - parent_pos[axis];
+                }
+            }
+            self.get_mut(id).rel_pos = rel_pos;
+        }
     }
 }
 
@@ -324,6 +877,17 @@ fn recursive_children_mut(w: &mut Widget, f: &mut dyn FnMut(&mut Widget)) {
         recursive_children_mut(child, f);
     }
 }
+fn tab_order_recurse(w: &Widget, to_remove: &[Id], order: &mut Vec<(Option<i32>, Id)>) {
+    for child in w.children.values() {
+        if to_remove.contains(&child.get_id()) {
+            continue;
+        }
+        if child.focusable() {
+            order.push((child.config.tab_index, child.get_id()));
+        }
+        tab_order_recurse(child, to_remove, order);
+    }
+}
 fn update_paths_recurse(current_path: Vec<Id>, w: &mut Widget, paths: &mut IndexMap<Id, Vec<Id>>) {
     for child in w.children.values_mut() {
         paths.insert(child.get_id(), current_path.clone());
@@ -16,7 +16,8 @@ pub trait Interactive: Any + std::fmt::Debug + Send + Sync {
     }
     /// Optional additional logic specific to this widget type, called in the bottom-up phase, and
     /// thus `_local_events` is the accumulated events of all descendants of `self` and `self`
-    /// itself.
+    /// itself, except those that a descendant consumed with `WidgetContext::consume`.
+    /// (`WidgetContext::all_events` includes the consumed events as well.)
     /// Any logic handling interactive events in `self`, such as mouse press/release/hover/unhover
     /// should thus be implemented here.
     /// *Make sure that if any fields of `self` are changed, to generate emit a
@@ -35,6 +36,13 @@ pub trait Interactive: Any + std::fmt::Debug + Send + Sync {
         }
     }
 
+    /// Whether the widget can receive keyboard focus (see `Gui::focus`). Focusable widgets are
+    /// focused when pressed, and receive `EventKind::KeyPress` and `EventKind::KeyRelease`
+    /// while focused.
+    fn focusable(&self) -> bool {
+        false
+    }
+
     /// Defines an area which is considered "inside" a widget - for checking mouse hover etc.
     /// Provided implementation simply checks whether mouse is inside the boundaries, where `pos`
     /// is the very center of the widget. However, this is configurable in case a finer shape is
@@ -45,6 +53,35 @@ pub trait Interactive: Any + std::fmt::Debug + Send + Sync {
         mouse.y < max.y && mouse.y > min.y && mouse.x > min.x && mouse.x < max.x
     }
 
+    /// Offset that is added to the positions of all children after layout, for example to
+    /// scroll them.
+    fn content_offset(&self) -> Vec2 {
+        Vec2::zero()
+    }
+
+    /// Text that drawers should draw in the widget, for drawers that do not know the concrete type
+    /// of each widget (such as `DisplayListDrawer`).
+    fn text(&self) -> Option<&str> {
+        None
+    }
+    /// Whether `text` is broken into lines to fit the width of the widget (see
+    /// `TextCalculator::wrap_lines`)
+    fn wraps_text(&self) -> bool {
+        false
+    }
+    /// Image that drawers should stretch over the widget, for drawers that do not know the
+    /// concrete type of each widget. `gui` does not load images, so this is a key (such as a file
+    /// path) that the drawer understands.
+    fn image(&self) -> Option<&str> {
+        None
+    }
+
+    /// Set the intrinsic size that a drawer has measured, such as the size of a loaded image (see
+    /// `WidgetOp::SetIntrinsicSize`). Make sure to emit `EventKind::Change {..}` for any field
+    /// that changes.
+    /// Default ignores the size.
+    fn set_intrinsic_size(&mut self, _size: Vec2, _ctx: &mut WidgetContext) {}
+
     /// If the widget has some sort of intrinsic size, returns Some.
     /// Anything whose real size depends on the drawer (text, sprites, ..).
     /// NOTE: Only basic 'leaf' widgets like text need to implement this - it's not like it must be
@@ -53,5 +90,16 @@ pub trait Interactive: Any + std::fmt::Debug + Send + Sync {
     fn determine_size(&self, _drawer: &mut dyn TextCalculator) -> Option<Vec2> {
         None
     }
+    /// Like `determine_size`, but for widgets whose height depends on their width (such as
+    /// wrapped text). `width` is the width given by `SizeHint::External` or by the parent through
+    /// `SizeHint::Flex`, if known.
+    /// Default ignores `width` and calls `determine_size`.
+    fn determine_size_with_width(
+        &self,
+        drawer: &mut dyn TextCalculator,
+        _width: Option<f32>,
+    ) -> Option<Vec2> {
+        self.determine_size(drawer)
+    }
 }
 mopafy!(Interactive);
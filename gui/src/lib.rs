@@ -9,9 +9,8 @@
 //! The thought is thus that [GuiDrawer] can be implemented anew for each such application; but a
 //! default drawer (or multiple, for multiple backends) can be provided by `gui`.
 //!
-//! ## just an idea
-//! could make a GuiDrawer that emits primitives (for example SVG primitives or something), which
-//! is intercepted somewhere else by some renderer
+//! Alternatively, [DisplayListDrawer] emits renderer-agnostic primitives ([DrawCommand]), so that a
+//! backend only has to draw rectangles, text, images and clip rectangles.
 //!
 //! ## Layout
 //! The *main axis* is the axis along which widgets are stacked. The other axis is called the
@@ -23,6 +22,7 @@ extern crate mopa;
 extern crate derive_deref;
 
 use indexmap::IndexMap;
+use winit::event::{ModifiersState, MouseButton, VirtualKeyCode};
 use winput::Input;
 
 pub mod default;
@@ -115,10 +115,51 @@ impl Event {
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum EventKind {
-    Press,
-    Release,
+    /// A mouse button was pressed on the widget
+    Press {
+        button: MouseButton,
+    },
+    /// A mouse button that was pressed on the widget was released, wherever the mouse is
+    Release {
+        button: MouseButton,
+    },
+    /// A mouse button was pressed and then released on the widget
+    Click {
+        button: MouseButton,
+    },
+    /// The widget was clicked twice with the same button, within the time and distance of
+    /// `GuiInternal::set_double_click_threshold`
+    DoubleClick {
+        button: MouseButton,
+    },
     Hover,
     Unhover,
+    /// The mouse moved by `delta` while the widget has captured the pointer (see
+    /// `GuiInternal::capture_pointer`)
+    Drag {
+        delta: Vec2,
+    },
+    /// The mouse wheel moved by `delta` (in lines) over the widget, and none of its descendants
+    /// under the mouse consumed it (see `WidgetContext::consume`)
+    Scroll {
+        delta: Vec2,
+    },
+    /// The widget received keyboard focus
+    Focus,
+    /// The widget lost keyboard focus
+    Unfocus,
+    /// A key was pressed while the widget has focus
+    KeyPress {
+        key: VirtualKeyCode,
+        modifiers: ModifiersState,
+    },
+    /// A key was released while the widget has focus
+    KeyRelease {
+        key: VirtualKeyCode,
+        modifiers: ModifiersState,
+    },
+    /// A character was typed while the widget has focus
+    Char(char),
     /// Change to any field of Widget or Interactive
     Change {
         field: FieldId,
@@ -150,7 +191,9 @@ impl EventKind {
 
 #[derive(Default, Debug, Copy, Clone)]
 pub struct Capture {
+    /// Whether a widget under the mouse captures the mouse
     pub mouse: bool,
+    /// Whether the focused widget captures the keyboard
     pub keyboard: bool,
 }
 impl std::ops::BitOrAssign for Capture {
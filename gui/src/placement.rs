@@ -1,3 +1,5 @@
+use crate::Id;
+
 #[derive(Copy, Clone, Debug)]
 pub struct Placement {
     pub x: PlacementAxis,
@@ -8,11 +10,25 @@ pub struct Placement {
 
 #[derive(Copy, Clone, Debug)]
 pub enum PlacementAxis {
-    // Percentage(f32),
+    /// Percentage of the size of the parent
+    Percentage(f32),
     Fixed(f32),
+    /// Relative to another widget, given by `id`, anywhere in the tree. The anchor decides which
+    /// edge of the other widget the offset is measured from:
+    /// - `Anchor::Min`: the min edges of both widgets are aligned
+    /// - `Anchor::Center`: the centers of both widgets are aligned
+    /// - `Anchor::Max`: the min edge of this widget is aligned with the max edge of the other
+    ///
+    /// For example, a dropdown list below a button is placed with `Anchor::Min` along x and
+    /// `Anchor::Max` along y.
+    ///
+    /// The other widget should not itself be placed relative to yet another widget.
+    Relative {
+        id: Id,
+        offset: f32,
+    },
 }
 // each axis has an anchor
-// each axis can be Float, Fixed(f32), Percentage(f32)
 
 impl Placement {
     pub fn fixed(x: f32, y: f32) -> Self {
@@ -23,6 +39,22 @@ impl Placement {
             y_anchor: Anchor::Min,
         }
     }
+    pub fn percentage(x: f32, y: f32) -> Self {
+        Self {
+            x: PlacementAxis::Percentage(x),
+            y: PlacementAxis::Percentage(y),
+            x_anchor: Anchor::Min,
+            y_anchor: Anchor::Min,
+        }
+    }
+    pub fn relative(id: Id, x: f32, y: f32) -> Self {
+        Self {
+            x: PlacementAxis::Relative { id, offset: x },
+            y: PlacementAxis::Relative { id, offset: y },
+            x_anchor: Anchor::Min,
+            y_anchor: Anchor::Min,
+        }
+    }
     pub fn x_anchor(mut self, a: Anchor) -> Self {
         self.x_anchor = a;
         self
@@ -44,9 +76,22 @@ impl Placement {
             y_anchor: Anchor::Center,
         }
     }
+    /// Get placement and anchor along `axis`
+    pub fn axis(&self, axis: Axis) -> (PlacementAxis, Anchor) {
+        match axis {
+            Axis::X => (self.x, self.x_anchor),
+            Axis::Y => (self.y, self.y_anchor),
+        }
+    }
+    pub fn is_relative(&self) -> bool {
+        [self.x, self.y].iter().any(|axis| match axis {
+            PlacementAxis::Relative { .. } => true,
+            _ => false,
+        })
+    }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Axis {
     X,
     Y,
@@ -67,6 +112,156 @@ pub enum Anchor {
     Max,
 }
 
+/// How free space along the main axis is distributed among children.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Justify {
+    /// Children are packed together and positioned according to `WidgetConfig::layout_main_align`.
+    Packed,
+    /// Free space is put between children, so that the first and last child touch the edges.
+    SpaceBetween,
+    /// Free space is split equally around each child, so the gaps at the edges are half as big
+    /// as the gaps between children.
+    SpaceAround,
+    /// Free space is split so that all gaps, including those at the edges, are equal.
+    SpaceEvenly,
+}
+impl Default for Justify {
+    fn default() -> Self {
+        Justify::Packed
+    }
+}
+
+/// Definition of the size of a column or row in a [Grid].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Track {
+    Fixed(f32),
+    /// Fits the largest child in the track.
+    Auto,
+    /// Fraction of the space that is left after all other tracks are sized. The space is shared
+    /// with other fraction tracks in proportion to the fractions.
+    /// If the size of the parent is not known (`SizeHint::Minimize`), behaves like `Track::Auto`.
+    Fraction(f32),
+}
+
+/// Maximum number of columns or rows that a [Grid] can define
+pub const MAX_GRID_TRACKS: usize = 16;
+
+/// The defined columns or rows of a [Grid]. They are stored inline, so that `WidgetConfig` is
+/// `Copy`. Dereferences to a slice.
+#[derive(Copy, Clone)]
+pub struct Tracks {
+    tracks: [Track; MAX_GRID_TRACKS],
+    len: usize,
+}
+impl Tracks {
+    /// Returns `None` if there are more than `MAX_GRID_TRACKS` tracks.
+    pub fn new(tracks: &[Track]) -> Option<Self> {
+        if tracks.len() > MAX_GRID_TRACKS {
+            return None;
+        }
+        let mut inline = [Track::Auto; MAX_GRID_TRACKS];
+        inline[..tracks.len()].copy_from_slice(tracks);
+        Some(Tracks {
+            tracks: inline,
+            len: tracks.len(),
+        })
+    }
+}
+impl std::ops::Deref for Tracks {
+    type Target = [Track];
+    fn deref(&self) -> &[Track] {
+        &self.tracks[..self.len]
+    }
+}
+impl Default for Tracks {
+    fn default() -> Self {
+        Tracks {
+            tracks: [Track::Auto; MAX_GRID_TRACKS],
+            len: 0,
+        }
+    }
+}
+impl PartialEq for Tracks {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+impl std::fmt::Debug for Tracks {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// Configuration of a grid layout (see `WidgetConfig::grid`).
+/// At most `MAX_GRID_TRACKS` columns and `MAX_GRID_TRACKS` rows can be defined (so that
+/// `WidgetConfig` is `Copy`). If children are put in columns or rows beyond the defined ones,
+/// those are `Track::Auto`, without any limit on their number.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Grid {
+    pub columns: Tracks,
+    pub rows: Tracks,
+    /// Space between columns
+    pub column_gap: f32,
+    /// Space between rows
+    pub row_gap: f32,
+}
+impl Grid {
+    /// Returns `None` if there are more than `MAX_GRID_TRACKS` columns or rows.
+    pub fn new(columns: &[Track], rows: &[Track]) -> Option<Self> {
+        Some(Grid {
+            columns: Tracks::new(columns)?,
+            rows: Tracks::new(rows)?,
+            column_gap: 0.0,
+            row_gap: 0.0,
+        })
+    }
+    pub fn gap(mut self, column_gap: f32, row_gap: f32) -> Self {
+        self.column_gap = column_gap;
+        self.row_gap = row_gap;
+        self
+    }
+}
+
+/// Position of a child in the grid of its parent (see `WidgetConfig::grid_cell`).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GridCell {
+    pub column: usize,
+    pub row: usize,
+    /// Number of columns spanned. At least 1.
+    pub column_span: usize,
+    /// Number of rows spanned. At least 1.
+    pub row_span: usize,
+}
+impl GridCell {
+    pub fn new(column: usize, row: usize) -> Self {
+        GridCell {
+            column,
+            row,
+            column_span: 1,
+            row_span: 1,
+        }
+    }
+    pub fn span(mut self, column_span: usize, row_span: usize) -> Self {
+        self.column_span = column_span.max(1);
+        self.row_span = row_span.max(1);
+        self
+    }
+    /// First column or row along `axis`
+    pub fn start(&self, axis: Axis) -> usize {
+        match axis {
+            Axis::X => self.column,
+            Axis::Y => self.row,
+        }
+    }
+    /// Number of columns or rows spanned along `axis`
+    pub fn span_along(&self, axis: Axis) -> usize {
+        match axis {
+            Axis::X => self.column_span,
+            Axis::Y => self.row_span,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum SizeHint {
     /// Size is given externally by application.
@@ -77,8 +272,12 @@ pub enum SizeHint {
     /// Size will be set to exactly contain children (plus eventual padding).
     Minimize,
     Intrinsic,
-    // Percentage(f32, f32),
-    // TODO ^ rather try "flex factors" like in Flutter
+    /// Size is given by the parent: the space left over along the parent's main axis after all
+    /// other children are laid out, is split among flex children in proportion to their factor.
+    /// Along the parent's cross axis, the child is stretched to fill the parent.
+    /// If the size of the parent is not known (`SizeHint::Minimize`), this behaves like
+    /// `SizeHint::Minimize`.
+    Flex(f32),
 }
 impl Default for SizeHint {
     fn default() -> Self {
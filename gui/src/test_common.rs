@@ -2,31 +2,66 @@ use crate::default;
 use crate::*;
 use slog::{o, Discard, Logger};
 use std::collections::HashMap;
-use winit::event::{ElementState, ModifiersState, MouseButton};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use winit::event::{
+    ElementState, KeyboardInput, ModifiersState, MouseButton, MouseScrollDelta, VirtualKeyCode,
+};
 use winput::{Input, MouseInput};
 
 pub type TextField = default::TextField<()>;
 pub type Button = default::Button<()>;
 pub type ToggleButton = default::ToggleButton<()>;
 pub type Select = default::Select<()>;
+pub type RadioGroup = default::RadioGroup<()>;
+pub type Checkbox = default::Checkbox<()>;
+pub type CheckboxGroup = default::CheckboxGroup<()>;
+pub type ScrollArea = default::ScrollArea<()>;
+pub type Slider = default::Slider<()>;
+pub type TextInput = default::TextInput<()>;
+pub type Image = default::Image<()>;
 
+/// A `Gui` with the input, logger and drawer context needed to simulate frames
 #[derive(Deref, DerefMut)]
-pub struct TestGui {
+pub struct TestGui<D: GuiDrawer = NoDrawer> {
     #[deref_target]
-    pub gui: Gui<NoDrawer>,
+    pub gui: Gui<D>,
     pub input: Input,
     pub log: Logger,
+    /// Passed to the drawer in every update
+    pub ctx: D::Context,
+    /// Time of the frames (see `Gui::set_time`)
+    time: Instant,
 }
 impl TestGui {
     pub fn new() -> Self {
+        TestGui::with_drawer(NoDrawer, ())
+    }
+}
+impl<D: GuiDrawer> TestGui<D> {
+    pub fn with_drawer(drawer: D, mut ctx: D::Context) -> Self {
+        let time = Instant::now();
+        let mut gui = Gui::new(drawer, &mut ctx);
+        gui.set_time(Some(time));
         TestGui {
             log: Logger::root(Discard, o!()),
-            gui: Gui::new(NoDrawer, &mut ()),
+            gui,
             input: Input::default(),
+            ctx,
+            time,
         }
     }
+    /// Let `duration` pass before the next frame. Time does not pass otherwise.
+    pub fn advance_time(&mut self, duration: Duration) {
+        self.time += duration;
+        self.gui.set_time(Some(self.time));
+    }
     fn update_internal(&mut self) -> (Vec<Event>, Capture) {
-        let (events, capture) = self.gui.update(&self.input, self.log.clone(), &mut ());
+        let (events, capture) = self
+            .gui
+            .update(&self.input, self.log.clone(), &mut self.ctx);
 
         println!("[TestGui.update] events = [");
         for event in events.iter() {
@@ -62,6 +97,67 @@ impl TestGui {
         release_left_mouse(&mut self.input);
         self.update_internal()
     }
+    /// Simulate a frame in which user presses `button` at `pos`.
+    pub fn press_button(&mut self, pos: Vec2, button: MouseButton) -> (Vec<Event>, Capture) {
+        self.input.prepare_for_next_frame();
+        self.input.register_mouse_position(pos.x, pos.y);
+        self.input
+            .register_mouse_input(&ElementState::Pressed, &button);
+        self.update_internal()
+    }
+    /// Simulate a frame in which user releases `button`.
+    pub fn release_button(&mut self, button: MouseButton) -> (Vec<Event>, Capture) {
+        self.input.prepare_for_next_frame();
+        self.input
+            .register_mouse_input(&ElementState::Released, &button);
+        self.update_internal()
+    }
+    /// Simulate a frame in which user moves the mouse to `pos`.
+    pub fn move_mouse(&mut self, pos: Vec2) -> (Vec<Event>, Capture) {
+        self.input.prepare_for_next_frame();
+        self.input.register_mouse_position(pos.x, pos.y);
+        self.update_internal()
+    }
+    /// Simulate a frame in which user scrolls the mouse wheel by `delta` lines at `pos`.
+    pub fn scroll(&mut self, pos: Vec2, delta: Vec2) -> (Vec<Event>, Capture) {
+        self.input.prepare_for_next_frame();
+        self.input.register_mouse_position(pos.x, pos.y);
+        self.input
+            .register_mouse_wheel(&MouseScrollDelta::LineDelta(delta.x, delta.y));
+        self.update_internal()
+    }
+    /// Simulate a frame in which user presses `key`.
+    pub fn press_key(&mut self, key: VirtualKeyCode) -> (Vec<Event>, Capture) {
+        self.input.prepare_for_next_frame();
+        self.input
+            .register_key(&keyboard_input(key, ElementState::Pressed));
+        self.update_internal()
+    }
+    /// Simulate a frame in which user types `text`.
+    pub fn type_text(&mut self, text: &str) -> (Vec<Event>, Capture) {
+        self.input.prepare_for_next_frame();
+        for c in text.chars() {
+            self.input.register_char(c);
+        }
+        self.update_internal()
+    }
+    /// Simulate a frame in which user releases `key`.
+    pub fn release_key(&mut self, key: VirtualKeyCode) -> (Vec<Event>, Capture) {
+        self.input.prepare_for_next_frame();
+        self.input
+            .register_key(&keyboard_input(key, ElementState::Released));
+        self.update_internal()
+    }
+}
+
+#[allow(deprecated)]
+fn keyboard_input(key: VirtualKeyCode, state: ElementState) -> KeyboardInput {
+    KeyboardInput {
+        scancode: 0,
+        state,
+        virtual_keycode: Some(key),
+        modifiers: ModifiersState::default(),
+    }
 }
 
 pub struct Expected {
@@ -290,3 +386,82 @@ pub fn print_widget_tree<D: GuiDrawer, F: Fn(&Widget) -> String>(gui: &Gui<D>, i
 
     print_tree(&tree).unwrap();
 }
+
+/// Compare `image` with the golden image at `path` (an 8-bit RGBA PNG), and panic if they differ.
+/// The actual image is then written next to the golden image, with the extension `.actual.png`.
+/// If the environment variable `UPDATE_GOLDEN` is set, `image` is written to `path` as the new
+/// golden image instead.
+/// Panics if `image` is empty (for example from `RasterDrawer::new(0, ..)`), because a PNG can
+/// not have a width or height of 0.
+pub fn assert_golden_image<P: AsRef<Path>>(image: &RgbaImage, path: P) {
+    let path = path.as_ref();
+    assert!(
+        image.width() > 0 && image.height() > 0,
+        "can not compare an empty image ({}x{}) with golden image {}",
+        image.width(),
+        image.height(),
+        path.display()
+    );
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        write_png(image, path);
+        return;
+    }
+    let golden = read_png(path).unwrap_or_else(|e| {
+        panic!(
+            "could not read golden image {}: {} (set UPDATE_GOLDEN to create it)",
+            path.display(),
+            e
+        )
+    });
+    if golden != *image {
+        let actual = path.with_extension("actual.png");
+        write_png(image, &actual);
+        let differing = if (golden.width(), golden.height()) == (image.width(), image.height()) {
+            golden
+                .as_raw()
+                .chunks(4)
+                .zip(image.as_raw().chunks(4))
+                .filter(|(a, b)| a != b)
+                .count()
+                .to_string()
+        } else {
+            "all".to_string()
+        };
+        panic!(
+            "image differs from golden image {} in {} pixels; see {}",
+            path.display(),
+            differing,
+            actual.display()
+        );
+    }
+}
+
+fn write_png(image: &RgbaImage, path: &Path) {
+    let file = BufWriter::new(File::create(path).unwrap());
+    let mut encoder = png::Encoder::new(file, image.width(), image.height());
+    encoder.set_color(png::ColorType::RGBA);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(image.as_raw()))
+        .unwrap_or_else(|e| panic!("could not write {}: {}", path.display(), e));
+}
+
+fn read_png(path: &Path) -> Result<RgbaImage, Box<dyn std::error::Error>> {
+    let (info, mut reader) = png::Decoder::new(File::open(path)?).read_info()?;
+    if (info.color_type, info.bit_depth) != (png::ColorType::RGBA, png::BitDepth::Eight) {
+        return Err("not an 8-bit RGBA PNG".into());
+    }
+    let mut data = vec![0; info.buffer_size()];
+    reader.next_frame(&mut data)?;
+    Ok(RgbaImage::from_raw(info.width, info.height, data).unwrap())
+}
+
+#[test]
+#[should_panic(expected = "can not compare an empty image (0x3)")]
+fn test_golden_image_empty() {
+    assert_golden_image(&RgbaImage::new(0, 3, [0; 4]), "empty.png");
+}
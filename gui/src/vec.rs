@@ -13,6 +13,31 @@ impl Rect {
             max: Vec2::zero(),
         }
     }
+    pub fn new(min: Vec2, max: Vec2) -> Self {
+        Rect { min, max }
+    }
+    /// Rectangle at `pos` with size `size`
+    pub fn from_pos_size(pos: Vec2, size: Vec2) -> Self {
+        Rect {
+            min: pos,
+            max: pos + size,
+        }
+    }
+    pub fn size(&self) -> Vec2 {
+        self.max - self.min
+    }
+    pub fn contains(&self, p: Vec2) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x && p.y >= self.min.y && p.y <= self.max.y
+    }
+    /// The overlap of two rectangles. If they do not overlap, the result has zero size.
+    pub fn intersection(&self, other: Rect) -> Rect {
+        let min = Vec2::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Vec2::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+        Rect {
+            min,
+            max: Vec2::new(max.x.max(min.x), max.y.max(min.y)),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
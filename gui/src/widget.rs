@@ -4,7 +4,8 @@ use crate::{
 };
 use indexmap::IndexMap;
 use slog::Logger;
-use std::ops::Deref;
+use std::{ops::Deref, time::Instant};
+use winit::event::MouseButton;
 use winput::Input;
 
 mod layout;
@@ -15,8 +16,15 @@ pub use layout::WidgetConfig;
 /// so that the compiler knows that only `self.children` is borrowed.
 macro_rules! widget_context {
     ($self:ident) => {
+        widget_context!($self, $self.gui.borrow().events().len())
+    };
+    ($self:ident, $events_start:expr) => {
         WidgetContext {
             self_id: $self.id,
+            pos: $self.pos,
+            size: $self.size,
+            layer: $self.layer,
+            events_start: $events_start,
             children: &mut $self.children,
             gui: &$self.gui,
         }
@@ -44,6 +52,14 @@ pub struct Widget {
     /// Current layer as calculated by layout algorithm.
     /// Depends only on `WidgetConfig::place`
     pub layer: u32,
+    /// The region that this widget is visible within, as calculated by the layout algorithm.
+    /// It is the intersection of the bounds of all ancestors with `WidgetConfig::clip_children`,
+    /// or `None` if there are no such ancestors. Drawers should cut off the widget accordingly.
+    /// (should be read-only outside `gui`)
+    pub clip: Option<Rect>,
+    /// Size given by the parent to axes with `SizeHint::Flex`. `None` if the parent did not
+    /// determine a size for that axis.
+    flex_size: Vec2<Option<f32>>,
 
     pub config: WidgetConfig,
 
@@ -51,8 +67,11 @@ pub struct Widget {
 
     /// Keeps track of hover state in order to generate the right WidgetEvents
     inside: bool,
-    /// Keeps track of mouse press state in order to generate the right WidgetEvents
-    pressed: bool,
+    /// Keeps track of mouse press state in order to generate the right WidgetEvents: the buttons
+    /// that were pressed on this widget and are not yet released
+    pressed: Vec<MouseButton>,
+    /// Button, time and mouse position of the last click, to detect double-clicks
+    last_click: Option<(MouseButton, Instant, Vec2)>,
 
     /// For internal use; mirrors the id that is the key in the HashMap that this Widget is
     /// likely a part of.
@@ -66,6 +85,10 @@ impl Widget {
         let mut children = IndexMap::new();
         let mut ctx = WidgetContext {
             self_id: id,
+            pos: Vec2::zero(),
+            size: Vec2::zero(),
+            layer: 0,
+            events_start: gui.borrow().events().len(),
             children: &mut children,
             gui: &gui,
         };
@@ -77,17 +100,21 @@ impl Widget {
             rel_pos: Vec2::zero(),
             size: Vec2::new(10.0, 10.0),
             layer: 0,
+            clip: None,
+            flex_size: Vec2::new(None, None),
             config,
             gui,
 
             inside: false,
-            pressed: false,
+            pressed: Vec::new(),
+            last_click: None,
             id,
         }
     }
     /// Remove child for real - only for internal use.
     pub(crate) fn remove(&mut self, id: Id) -> Option<()> {
-        self.children.remove(&id).map(drop)
+        // (`shift_remove` preserves the order of the remaining children)
+        self.children.shift_remove(&id).map(drop)
     }
 
     /// Creates a lens to access this widget.
@@ -115,14 +142,20 @@ impl Widget {
     pub(crate) fn widget_context(&mut self) -> WidgetContext {
         widget_context!(self)
     }
+    /// Calls `Interactive::set_intrinsic_size` (see `WidgetOp::SetIntrinsicSize`)
+    pub(crate) fn set_intrinsic_size(&mut self, size: Vec2) {
+        let mut ctx = widget_context!(self);
+        self.inner.set_intrinsic_size(size, &mut ctx)
+    }
     pub fn get_id(&self) -> Id {
         self.id
     }
     pub fn hover(&self) -> bool {
         self.inside
     }
+    /// Whether any mouse button that was pressed on this widget is still held
     pub fn pressed(&self) -> bool {
-        self.pressed
+        !self.pressed.is_empty()
     }
     /// Main update work happens here.
     /// Bottom-up means postfix
@@ -145,9 +178,25 @@ impl Widget {
             capture |= child_capture;
         }
 
+        let pointer_capture = self.gui.borrow().pointer_capture();
+        if pointer_capture == Some(self.id) {
+            // Mouse movement goes to the widget that captured the pointer, wherever the mouse is
+            let mut gui = self.gui.borrow_mut();
+            let delta = gui.mouse_delta();
+            if delta != Vec2::zero() {
+                gui.push_event(Event::new(self.id, EventKind::Drag { delta }));
+            }
+        }
+
+        // Parts of the widget that are cut off by an ancestor are not under the mouse, and while
+        // another widget has captured the pointer, nothing else is
+        let under_mouse = self.inside(self.pos, self.size, mouse)
+            && self.clip.map(|clip| clip.contains(mouse)).unwrap_or(true)
+            && pointer_capture.map(|id| id == self.id).unwrap_or(true);
+
         if !capture.mouse {
             let mut gui = self.gui.borrow_mut();
-            let now_inside = self.inside(self.pos, self.size, mouse);
+            let now_inside = under_mouse;
             let prev_inside = self.inside;
             self.inside = now_inside;
 
@@ -158,30 +207,95 @@ impl Widget {
             }
 
             if now_inside {
-                capture |= self.inner.captures();
+                // Keyboard capture is determined by focus, in `Gui::update`
+                capture.mouse |= self.inner.captures().mouse;
             }
 
-            if now_inside && input.is_mouse_button_toggled_down(winit::event::MouseButton::Left) {
-                self.pressed = true;
-                gui.push_event(Event::new(self.id, EventKind::Press));
+            if now_inside {
+                for button in gui.button_presses().to_vec() {
+                    self.pressed.push(button);
+                    gui.push_event(Event::new(self.id, EventKind::Press { button }));
+                    if button == MouseButton::Left && self.inner.focusable() {
+                        gui.claim_click_focus(self.id);
+                    }
+                }
+            }
+            for button in gui.button_releases().to_vec() {
+                if let Some(idx) = self.pressed.iter().position(|b| *b == button) {
+                    self.pressed.remove(idx);
+                    gui.push_event(Event::new(self.id, EventKind::Release { button }));
+                    if now_inside {
+                        gui.push_event(Event::new(self.id, EventKind::Click { button }));
+                        let now = gui.now();
+                        let double_click = self
+                            .last_click
+                            .map(|(last_button, time, pos)| {
+                                last_button == button
+                                    && now.duration_since(time) < gui.double_click_time()
+                                    && (mouse - pos).length() <= gui.double_click_distance()
+                            })
+                            .unwrap_or(false);
+                        if double_click {
+                            gui.push_event(Event::new(self.id, EventKind::DoubleClick { button }));
+                            self.last_click = None;
+                        } else {
+                            self.last_click = Some((button, now, mouse));
+                        }
+                    }
+                }
             }
-            if self.pressed && input.is_mouse_button_toggled_up(winit::event::MouseButton::Left) {
-                self.pressed = false;
-                gui.push_event(Event::new(self.id, EventKind::Release));
+        }
+        // The mouse wheel goes to the innermost widget under the mouse, and bubbles up to its
+        // ancestors (which are updated later) until one of them consumes it
+        {
+            let mut gui = self.gui.borrow_mut();
+            let delta = gui.scroll_delta();
+            if under_mouse && delta != Vec2::zero() && !gui.scroll_consumed() {
+                gui.push_event(Event::new(self.id, EventKind::Scroll { delta }));
+            }
+        }
+        // Keyboard input goes to the focused widget only
+        {
+            let mut gui = self.gui.borrow_mut();
+            if gui.focused() == Some(self.id) {
+                for kind in gui.keyboard_events() {
+                    gui.push_event(Event::new(self.id, kind));
+                }
             }
         }
         // Execute widget-specific logic
-        let local_events = self.gui.borrow().events()[prev_events_len..].to_vec();
-        self.inner
-            .update(self.id, local_events, &mut widget_context!(self));
+        // Events consumed by a descendant do not propagate further up
+        let local_events = {
+            let gui = self.gui.borrow();
+            (prev_events_len..gui.events().len())
+                .filter(|idx| !gui.is_consumed(*idx))
+                .map(|idx| gui.events()[idx].clone())
+                .collect()
+        };
+        self.inner.update(
+            self.id,
+            local_events,
+            &mut widget_context!(self, prev_events_len),
+        );
 
         capture
     }
     /// Everything that needs to be calculated top-down:
-    /// absolute positions and layer numbers
+    /// absolute positions, layer numbers and clip rectangles
     pub(crate) fn update_top_down(&mut self) {
-        let pos = self.pos;
+        let pos = self.pos + self.inner.content_offset();
+        let children_clip = if self.config.clip_children {
+            let rect = Rect::from_pos_size(self.pos, self.size);
+            Some(
+                self.clip
+                    .map(|clip| clip.intersection(rect))
+                    .unwrap_or(rect),
+            )
+        } else {
+            self.clip
+        };
         for child in self.children.values_mut() {
+            child.clip = children_clip;
             let new_pos = pos + child.rel_pos;
             if new_pos != child.pos {
                 self.gui
@@ -221,7 +335,16 @@ pub struct WidgetContext<'a, 'b> {
     /// children of a widget
     children: &'a mut IndexMap<Id, Widget>,
     pub self_id: Id,
+    /// Position of the widget as calculated by the last layout (zero in `Interactive::init`)
+    pub pos: Vec2,
+    /// Size of the widget as calculated by the last layout (zero in `Interactive::init`)
+    pub size: Vec2,
+    /// Layer of the widget as calculated by the last layout (zero in `Interactive::init`), for
+    /// example to measure text with `GuiInternal::text_calc`
+    pub layer: u32,
     pub gui: &'b GuiShared,
+    /// Index of the first event of the widget and its descendants in the current update
+    events_start: usize,
 }
 impl<'a, 'b> Deref for WidgetContext<'a, 'b> {
     type Target = IndexMap<Id, Widget>;
@@ -230,6 +353,18 @@ impl<'a, 'b> Deref for WidgetContext<'a, 'b> {
     }
 }
 impl<'a, 'b> WidgetContext<'a, 'b> {
+    /// Stop `event` from propagating further up to the ancestors of the widget.
+    /// `event` should be one of the `local_events` given to `Interactive::update`.
+    pub fn consume(&mut self, event: &Event) {
+        self.gui
+            .borrow_mut()
+            .consume_event(self.events_start, event);
+    }
+    /// All events of the widget and its descendants in the current update, including those that
+    /// were consumed by a descendant
+    pub fn all_events(&self) -> Vec<Event> {
+        self.gui.borrow().events()[self.events_start..].to_vec()
+    }
     /// Insert child
     pub fn insert_child<I: Interactive>(&mut self, child: I) -> Id {
         let id = {
@@ -1,4 +1,6 @@
 use crate::*;
+use indexmap::IndexMap;
+use std::collections::HashSet;
 
 #[derive(Debug, Clone, Copy)]
 pub struct WidgetConfig {
@@ -8,20 +10,45 @@ pub struct WidgetConfig {
     pub place: Option<Placement>,
     /// The axis along which to stack children
     pub layout_direction: Axis,
-    /// Anchor along main axis
+    /// Anchor along main axis. Only used when `layout_justify` is `Justify::Packed`.
     pub layout_main_align: Anchor,
     /// If true, children are stacked in the cross axis when the main axis fills up.
+    /// Only has an effect when the size along the main axis is `SizeHint::External`.
     pub layout_wrap: bool,
     /// Alignment of children along the cross axis (the axis which is not the direction).
     pub layout_cross_align: Anchor,
     /// Space between widgets in the main axis.
-    /// TODO: should maybe be a "justify" enum where you can choose to space them evenly etc
+    /// When `layout_justify` spreads out children, this is the minimum space between them.
     pub layout_main_margin: f32,
+    /// Distribution of free space along the main axis.
+    pub layout_justify: Justify,
+    /// Space between lines in the cross axis, when children are wrapped (see `layout_wrap`).
+    pub layout_cross_margin: f32,
+
+    /// If `Some`, children are laid out in the cells of a grid instead of being stacked along
+    /// `layout_direction`. Children are then aligned within their cells according to
+    /// `layout_main_align` along `layout_direction` and `layout_cross_align` along the other axis.
+    pub grid: Option<Grid>,
+    /// The cell of the parent's grid that this widget occupies. If `None`, the widget is put in
+    /// the next free cell.
+    pub grid_cell: Option<GridCell>,
 
     pub padding: Rect,
 
     // size hints
     pub size_hint: Vec2<SizeHint>,
+    /// Optional lower bound of the size, applied after `size_hint` is resolved
+    pub min_size: Vec2<Option<f32>>,
+    /// Optional upper bound of the size, applied after `size_hint` is resolved.
+    /// If it is smaller than `min_size`, `min_size` wins.
+    pub max_size: Vec2<Option<f32>>,
+
+    /// If true, descendants are only visible (and can only be hovered and pressed) within the
+    /// boundaries of this widget. See `Widget::clip`.
+    pub clip_children: bool,
+    /// Overrides the position of this widget in the Tab order (see `Gui::tab_order`).
+    /// Only has an effect on focusable widgets.
+    pub tab_index: Option<i32>,
 }
 impl Default for WidgetConfig {
     fn default() -> Self {
@@ -32,10 +59,19 @@ impl Default for WidgetConfig {
             layout_main_align: Anchor::Min,
             layout_cross_align: Anchor::Min,
             layout_main_margin: 0.0,
+            layout_justify: Justify::Packed,
+            layout_cross_margin: 0.0,
+            grid: None,
+            grid_cell: None,
 
             padding: Rect::zero(),
 
             size_hint: Vec2::default(),
+            min_size: Vec2::new(None, None),
+            max_size: Vec2::new(None, None),
+
+            clip_children: false,
+            tab_index: None,
         }
     }
 }
@@ -70,10 +106,66 @@ impl WidgetConfig {
         self.layout_direction = value;
         self
     }
+    pub fn set_layout_direction(&mut self, value: Axis) -> &mut Self {
+        self.layout_direction = value;
+        self
+    }
+    pub fn layout_main_align(mut self, value: Anchor) -> Self {
+        self.layout_main_align = value;
+        self
+    }
+    pub fn set_layout_main_align(&mut self, value: Anchor) -> &mut Self {
+        self.layout_main_align = value;
+        self
+    }
     pub fn layout_cross_align(mut self, value: Anchor) -> Self {
         self.layout_cross_align = value;
         self
     }
+    pub fn set_layout_cross_align(&mut self, value: Anchor) -> &mut Self {
+        self.layout_cross_align = value;
+        self
+    }
+    pub fn layout_justify(mut self, value: Justify) -> Self {
+        self.layout_justify = value;
+        self
+    }
+    pub fn set_layout_justify(&mut self, value: Justify) -> &mut Self {
+        self.layout_justify = value;
+        self
+    }
+    pub fn layout_wrap(mut self, value: bool) -> Self {
+        self.layout_wrap = value;
+        self
+    }
+    pub fn set_layout_wrap(&mut self, value: bool) -> &mut Self {
+        self.layout_wrap = value;
+        self
+    }
+    pub fn layout_cross_margin(mut self, value: f32) -> Self {
+        self.layout_cross_margin = value;
+        self
+    }
+    pub fn set_layout_cross_margin(&mut self, value: f32) -> &mut Self {
+        self.layout_cross_margin = value;
+        self
+    }
+    pub fn grid(mut self, grid: Grid) -> Self {
+        self.grid = Some(grid);
+        self
+    }
+    pub fn set_grid(&mut self, grid: Grid) -> &mut Self {
+        self.grid = Some(grid);
+        self
+    }
+    pub fn grid_cell(mut self, cell: GridCell) -> Self {
+        self.grid_cell = Some(cell);
+        self
+    }
+    pub fn set_grid_cell(&mut self, cell: GridCell) -> &mut Self {
+        self.grid_cell = Some(cell);
+        self
+    }
     pub fn placement(mut self, place: Placement) -> Self {
         self.place = Some(place);
         self
@@ -113,6 +205,56 @@ impl WidgetConfig {
         self.size_hint.y = SizeHint::External(h);
         self
     }
+    pub fn min_width(mut self, w: f32) -> Self {
+        self.min_size.x = Some(w);
+        self
+    }
+    pub fn min_height(mut self, h: f32) -> Self {
+        self.min_size.y = Some(h);
+        self
+    }
+    pub fn max_width(mut self, w: f32) -> Self {
+        self.max_size.x = Some(w);
+        self
+    }
+    pub fn max_height(mut self, h: f32) -> Self {
+        self.max_size.y = Some(h);
+        self
+    }
+    pub fn set_min_size(&mut self, w: Option<f32>, h: Option<f32>) -> &mut Self {
+        self.min_size = Vec2::new(w, h);
+        self
+    }
+    pub fn set_max_size(&mut self, w: Option<f32>, h: Option<f32>) -> &mut Self {
+        self.max_size = Vec2::new(w, h);
+        self
+    }
+    pub fn clip_children(mut self, value: bool) -> Self {
+        self.clip_children = value;
+        self
+    }
+    pub fn set_clip_children(&mut self, value: bool) -> &mut Self {
+        self.clip_children = value;
+        self
+    }
+    pub fn tab_index(mut self, value: i32) -> Self {
+        self.tab_index = Some(value);
+        self
+    }
+    pub fn set_tab_index(&mut self, value: Option<i32>) -> &mut Self {
+        self.tab_index = value;
+        self
+    }
+    /// Clamps `size` along `axis` to `min_size` and `max_size`
+    pub fn constrain(&self, axis: Axis, mut size: f32) -> f32 {
+        if let Some(max) = self.max_size[axis] {
+            size = size.min(max);
+        }
+        if let Some(min) = self.min_size[axis] {
+            size = size.max(min);
+        }
+        size
+    }
     pub fn padding(mut self, top: f32, bot: f32, left: f32, right: f32) -> Self {
         self.padding.min = Vec2::new(left, top);
         self.padding.max = Vec2::new(right, bot);
@@ -125,6 +267,15 @@ impl WidgetConfig {
     }
 }
 
+/// A run of children along the main axis. Without wrapping, there is only one line.
+struct Line {
+    children: Vec<Id>,
+    /// Extent of the line along the main axis, including margins between children
+    main_size: f32,
+    /// Extent of the line along the cross axis - that of its largest child
+    cross_size: f32,
+}
+
 impl Widget {
     /// Recursively updates the position of children, and updates size of `self` if applicable.
     /// Additionally, updates sizes of text fields using `GuiDrawer`
@@ -135,100 +286,490 @@ impl Widget {
         }
 
         // println!("Positioning Parent [{}]", self.id);
-        if self.config.layout_wrap {
-            unimplemented!()
+        // Size along each axis, if it is known without looking at the children
+        let width = match self.config.size_hint.x {
+            SizeHint::External(s) => Some(s),
+            SizeHint::Flex(_) => self.flex_size.x,
+            _ => None,
         }
+        .map(|s| self.config.constrain(Axis::X, s));
+        let intrinsic_size =
+            self.determine_size_with_width(self.gui.borrow_mut().text_calc(self.layer), width);
+        let mut known_size: Vec2<Option<f32>> = Vec2::new(None, None);
+        for &axis in &[Axis::X, Axis::Y] {
+            known_size[axis] = match self.config.size_hint[axis] {
+                SizeHint::Minimize => None,
+                SizeHint::External(s) => Some(s),
+                SizeHint::Intrinsic => Some(intrinsic_size.expect("no intrinsic size")[axis]),
+                SizeHint::Flex(_) => self.flex_size[axis],
+            }
+            .map(|s| self.config.constrain(axis, s));
+        }
+
+        let grid = self.config.grid;
+        let layout = |widget: &mut Widget| match grid {
+            Some(grid) => widget.layout_grid(&grid, known_size),
+            None => widget.layout_stack(known_size),
+        };
+        if layout(self) {
+            // Children whose height depends on the width they got (like wrapped text) have to be
+            // measured again
+            layout(self);
+        }
+        self.layout_placed();
+    }
+
+    /// Stacks children along `layout_direction`, wrapping them into several lines if enabled.
+    /// Returns true if any child with `SizeHint::Flex` changed size along the other axis than
+    /// the one given by `self` (which means that the size of `self` may be outdated).
+    fn layout_stack(&mut self, known_size: Vec2<Option<f32>>) -> bool {
+        let layout_main_align = self.config.layout_main_align;
         let layout_cross_align = self.config.layout_cross_align;
+        let layout_justify = self.config.layout_justify;
         let layout_main_margin = self.config.layout_main_margin;
-        let padding_min = self.config.padding.min;
+        let layout_cross_margin = self.config.layout_cross_margin;
+        let padding = self.config.padding;
 
         let (main_axis, cross_axis) = (
             self.config.layout_direction,
             self.config.layout_direction.other(),
         );
 
+        // Children with `SizeHint::Flex` only get their size from `self` if the size of `self` is
+        // known. Until then they do not take up any space.
+        let extent = |child: &Widget, axis: Axis| match child.config.size_hint[axis] {
+            SizeHint::Flex(_) if known_size[axis].is_some() => 0.0,
+            _ => child.size[axis],
+        };
+
+        //
+        // Split children into lines. Wrapping is only possible when the main axis size is known
+        // before looking at the children.
+        //
+        let max_line_size = match known_size[main_axis] {
+            Some(s) if self.config.layout_wrap => {
+                Some(s - padding.min[main_axis] - padding.max[main_axis])
+            }
+            _ => None,
+        };
+        let mut lines: Vec<Line> = Vec::new();
+        for child in self.children.values() {
+            if child.config.place.is_some() {
+                continue;
+            }
+            let child_main = extent(child, main_axis);
+            let child_cross = extent(child, cross_axis);
+            let fits = match (lines.last(), max_line_size) {
+                (Some(line), Some(max)) => line.main_size + layout_main_margin + child_main <= max,
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+            if fits {
+                let line = lines.last_mut().unwrap();
+                line.children.push(child.id);
+                line.main_size += layout_main_margin + child_main;
+                line.cross_size = line.cross_size.max(child_cross);
+            } else {
+                lines.push(Line {
+                    children: vec![child.id],
+                    main_size: child_main,
+                    cross_size: child_cross,
+                });
+            }
+        }
+
         //
         // Figure out size first, based on children's sizes
         //
 
-        let mut main_size = self.config.padding.min[main_axis] + self.config.padding.max[main_axis];
-        let mut cross_size = 0.0;
+        let main_size = padding.min[main_axis]
+            + padding.max[main_axis]
+            + lines.iter().map(|line| line.main_size).fold(0.0, f32::max);
+        let cross_size = padding.min[cross_axis]
+            + padding.max[cross_axis]
+            + lines.iter().map(|line| line.cross_size).sum::<f32>()
+            + layout_cross_margin * (lines.len().max(1) - 1) as f32;
+
+        let mut new_size = self.size;
+        new_size[main_axis] = self
+            .config
+            .constrain(main_axis, known_size[main_axis].unwrap_or(main_size));
+        new_size[cross_axis] = self
+            .config
+            .constrain(cross_axis, known_size[cross_axis].unwrap_or(cross_size));
+
+        self.update_size(new_size);
 
+        //
+        // Resolve sizes of flex children
+        //
+        let inner_size = self.size - padding.min - padding.max;
+
+        // A single line is aligned within the whole parent, whereas wrapped lines are each as
+        // thick as their thickest child.
+        if lines.len() == 1 {
+            lines[0].cross_size = inner_size[cross_axis];
+        }
+        let mut remeasure = false;
+        for line in lines.iter_mut() {
+            let main_sizes = if known_size[main_axis].is_some() {
+                self.distribute_flex(
+                    &line.children,
+                    main_axis,
+                    inner_size[main_axis] - line.main_size,
+                )
+            } else {
+                IndexMap::new()
+            };
+            for id in line.children.iter() {
+                let child = &mut self.children[id];
+                let mut flex_size = Vec2::new(None, None);
+                if let Some(&s) = main_sizes.get(id) {
+                    flex_size[main_axis] = Some(s);
+                    line.main_size += s;
+                }
+                if let SizeHint::Flex(_) = child.config.size_hint[cross_axis] {
+                    if known_size[cross_axis].is_some() {
+                        flex_size[cross_axis] = Some(line.cross_size);
+                    }
+                }
+                let prev_size = child.size;
+                child.set_flex_size(flex_size);
+                for &axis in &[main_axis, cross_axis] {
+                    if flex_size[axis].is_none() && child.size[axis] != prev_size[axis] {
+                        remeasure = true;
+                    }
+                }
+            }
+        }
+
+        //
+        // Update positions of children
+        //
+
+        // Keeps track of position along cross axis
+        let mut cross_progress = padding.min[cross_axis];
+        for line in lines {
+            let n = line.children.len() as f32;
+            let free = inner_size[main_axis] - line.main_size;
+            // Offset of the first child, and additional space between each pair of children
+            let (main_offset, extra_margin) = match layout_justify {
+                Justify::Packed => match layout_main_align {
+                    Anchor::Min => (0.0, 0.0),
+                    Anchor::Center => (free / 2.0, 0.0),
+                    Anchor::Max => (free, 0.0),
+                },
+                Justify::SpaceBetween if n > 1.0 => (0.0, free.max(0.0) / (n - 1.0)),
+                Justify::SpaceBetween => (0.0, 0.0),
+                Justify::SpaceAround => (free.max(0.0) / n / 2.0, free.max(0.0) / n),
+                Justify::SpaceEvenly => (free.max(0.0) / (n + 1.0), free.max(0.0) / (n + 1.0)),
+            };
+            // Keeps track of position along main axis
+            let mut main_progress = padding.min[main_axis] + main_offset;
+            for id in line.children {
+                let child = &mut self.children[&id];
+                let mut child_relative_pos = Vec2::zero();
+                child_relative_pos[main_axis] = main_progress;
+                child_relative_pos[cross_axis] = match layout_cross_align {
+                    Anchor::Min => cross_progress,
+                    Anchor::Center => {
+                        cross_progress + (line.cross_size - child.size[cross_axis]) / 2.0
+                    }
+                    Anchor::Max => cross_progress + line.cross_size - child.size[cross_axis],
+                };
+                main_progress += child.size[main_axis] + layout_main_margin + extra_margin;
+                child.rel_pos = child_relative_pos;
+            }
+            cross_progress += line.cross_size + layout_cross_margin;
+        }
+        remeasure
+    }
+
+    /// Sizes and positions children with `WidgetConfig::place`, which do not participate in the
+    /// layout of their siblings.
+    fn layout_placed(&mut self) {
+        let size = self.size;
+        let inner_size = size - self.config.padding.min - self.config.padding.max;
+        for child in self.children.values_mut() {
+            if let Some(place) = child.config.place {
+                // Flex children fill the parent
+                let mut flex_size = Vec2::new(None, None);
+                for &axis in &[Axis::X, Axis::Y] {
+                    if let SizeHint::Flex(_) = child.config.size_hint[axis] {
+                        flex_size[axis] = Some(inner_size[axis]);
+                    }
+                }
+                child.set_flex_size(flex_size);
+
+                let mut child_relative_pos = Vec2::zero();
+                for &axis in &[Axis::X, Axis::Y] {
+                    let offset = match place.axis(axis).0 {
+                        PlacementAxis::Fixed(offset) => offset,
+                        PlacementAxis::Percentage(p) => size[axis] * p / 100.0,
+                        // Resolved by `Gui` once the positions of all widgets are known
+                        PlacementAxis::Relative { .. } => continue,
+                    };
+                    child_relative_pos[axis] = match place.axis(axis).1 {
+                        Anchor::Min => offset,
+                        Anchor::Center => (size[axis] - child.size[axis]) / 2.0 + offset,
+                        Anchor::Max => size[axis] - child.size[axis] - offset,
+                    };
+                }
+                // println!("Positioning Child [{}] relative_pos={:?}", child.id, child_relative_pos);
+                child.rel_pos = child_relative_pos;
+            }
+        }
+    }
+
+    /// Splits `free` space along `axis` among the children in `ids` that have `SizeHint::Flex`,
+    /// in proportion to their flex factors. Children whose share would violate their min or max
+    /// size get that size instead, and the rest is split among the other children.
+    fn distribute_flex(&self, ids: &[Id], axis: Axis, free: f32) -> IndexMap<Id, f32> {
+        let mut frozen: IndexMap<Id, f32> = IndexMap::new();
+        loop {
+            let flexible = ids
+                .iter()
+                .filter_map(|id| match self.children[id].config.size_hint[axis] {
+                    SizeHint::Flex(f) if !frozen.contains_key(id) => Some((*id, f)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            let total_flex = flexible.iter().map(|(_, f)| f).sum::<f32>();
+            let remaining = (free - frozen.values().sum::<f32>()).max(0.0);
+
+            let mut sizes = frozen.clone();
+            let mut violated = false;
+            for (id, f) in flexible {
+                let share = if total_flex > 0.0 {
+                    remaining * f / total_flex
+                } else {
+                    0.0
+                };
+                let constrained = self.children[&id].config.constrain(axis, share);
+                if constrained != share {
+                    frozen.insert(id, constrained);
+                    violated = true;
+                }
+                sizes.insert(id, constrained);
+            }
+            if !violated {
+                return sizes;
+            }
+        }
+    }
+
+    /// Places children in the cells of `grid`.
+    /// Returns true if any child changed size along an axis where it does not fill its cell (which
+    /// means that the tracks and the size of `self` may be outdated).
+    fn layout_grid(&mut self, grid: &Grid, known_size: Vec2<Option<f32>>) -> bool {
+        let padding = self.config.padding;
+        let gap = Vec2::new(grid.column_gap, grid.row_gap);
+
+        //
+        // Assign cells to children: first those with a given cell, then the rest fill up free
+        // cells in row-major order
+        //
+        let mut cells: IndexMap<Id, GridCell> = IndexMap::new();
+        let mut occupied = HashSet::new();
+        let occupy = |cells: &mut IndexMap<Id, GridCell>,
+                      occupied: &mut HashSet<(usize, usize)>,
+                      id: Id,
+                      cell: GridCell| {
+            // (the fields are public, so the span may be zero despite `GridCell::span`)
+            let cell = cell.span(cell.column_span, cell.row_span);
+            for column in cell.column..cell.column + cell.column_span {
+                for row in cell.row..cell.row + cell.row_span {
+                    occupied.insert((column, row));
+                }
+            }
+            cells.insert(id, cell);
+        };
         for child in self.children.values() {
-            if let None = child.config.place {
-                main_size += child.size[main_axis] + layout_main_margin;
-                if child.size[cross_axis] > cross_size {
-                    cross_size = child.size[cross_axis]
+            if let (None, Some(cell)) = (child.config.place, child.config.grid_cell) {
+                occupy(&mut cells, &mut occupied, child.id, cell);
+            }
+        }
+        let n_columns = cells
+            .values()
+            .map(|cell| cell.column + cell.column_span)
+            .fold(grid.columns.len(), usize::max)
+            .max(1);
+        let mut cursor = 0;
+        for child in self.children.values() {
+            if let (None, None) = (child.config.place, child.config.grid_cell) {
+                while occupied.contains(&(cursor % n_columns, cursor / n_columns)) {
+                    cursor += 1;
                 }
+                let cell = GridCell::new(cursor % n_columns, cursor / n_columns);
+                occupy(&mut cells, &mut occupied, child.id, cell);
             }
         }
-        // because it should only be _between_ children - not after the last one
-        main_size -= layout_main_margin;
+        let n_rows = cells
+            .values()
+            .map(|cell| cell.row + cell.row_span)
+            .fold(grid.rows.len(), usize::max);
 
+        //
+        // Size of tracks, and then of self
+        //
+        let n_tracks = Vec2::new(n_columns, n_rows);
+        let mut tracks = Vec2::new(Vec::new(), Vec::new());
         let mut new_size = self.size;
+        for &(axis, defs) in &[(Axis::X, &grid.columns), (Axis::Y, &grid.rows)] {
+            let available = known_size[axis].map(|s| s - padding.min[axis] - padding.max[axis]);
+            tracks[axis] =
+                self.resolve_tracks(defs, n_tracks[axis], axis, &cells, available, gap[axis]);
+            let content_size = padding.min[axis]
+                + padding.max[axis]
+                + tracks[axis].iter().sum::<f32>()
+                + gap[axis] * n_tracks[axis].max(1).saturating_sub(1) as f32;
+            new_size[axis] = self
+                .config
+                .constrain(axis, known_size[axis].unwrap_or(content_size));
+        }
+        self.update_size(new_size);
+
+        //
+        // Size and position children within their cells
+        //
+        let main_axis = self.config.layout_direction;
+        let mut remeasure = false;
+        for (id, cell) in cells {
+            let child = &mut self.children[&id];
+            let mut flex_size = Vec2::new(None, None);
+            let mut cell_pos = Vec2::zero();
+            let mut cell_size = Vec2::zero();
+            for &axis in &[Axis::X, Axis::Y] {
+                let (start, span) = (cell.start(axis), cell.span_along(axis));
+                cell_pos[axis] = padding.min[axis]
+                    + tracks[axis][..start].iter().sum::<f32>()
+                    + gap[axis] * start as f32;
+                cell_size[axis] = tracks[axis][start..start + span].iter().sum::<f32>()
+                    + gap[axis] * (span - 1) as f32;
+                if let SizeHint::Flex(_) = child.config.size_hint[axis] {
+                    flex_size[axis] = Some(cell_size[axis]);
+                }
+            }
+            let prev_size = child.size;
+            child.set_flex_size(flex_size);
+            for &axis in &[Axis::X, Axis::Y] {
+                if flex_size[axis].is_none() && child.size[axis] != prev_size[axis] {
+                    remeasure = true;
+                }
+            }
+
+            let mut child_relative_pos = cell_pos;
+            for &axis in &[Axis::X, Axis::Y] {
+                let align = if axis == main_axis {
+                    self.config.layout_main_align
+                } else {
+                    self.config.layout_cross_align
+                };
+                child_relative_pos[axis] += match align {
+                    Anchor::Min => 0.0,
+                    Anchor::Center => (cell_size[axis] - child.size[axis]) / 2.0,
+                    Anchor::Max => cell_size[axis] - child.size[axis],
+                };
+            }
+            child.rel_pos = child_relative_pos;
+        }
+        remeasure
+    }
 
-        let intrinsic_size = self.determine_size(&mut *self.gui.borrow_mut().text_calc);
-        new_size[main_axis] = match self.config.size_hint[main_axis] {
-            SizeHint::Minimize => main_size,
-            SizeHint::External(s) => s,
-            SizeHint::Intrinsic => intrinsic_size.expect("no intrinsic size").x,
+    /// Calculates the sizes of `n` tracks (columns or rows) along `axis`, where `defs` are the
+    /// defined tracks and any others are `Track::Auto`. `available` is the space to share among
+    /// `Track::Fraction` tracks. If it is not known, these fit their content like `Track::Auto`.
+    fn resolve_tracks(
+        &self,
+        defs: &[Track],
+        n: usize,
+        axis: Axis,
+        cells: &IndexMap<Id, GridCell>,
+        available: Option<f32>,
+        gap: f32,
+    ) -> Vec<f32> {
+        let def = |i: usize| defs.get(i).cloned().unwrap_or(Track::Auto);
+        let fits_content = |i: usize| match def(i) {
+            Track::Fixed(_) => false,
+            Track::Auto => true,
+            Track::Fraction(_) => available.is_none(),
         };
-        new_size[cross_axis] = match self.config.size_hint[cross_axis] {
-            SizeHint::Minimize => {
-                cross_size
-                    + self.config.padding.min[cross_axis]
-                    + self.config.padding.max[cross_axis]
-            }
-            SizeHint::External(s) => s,
-            SizeHint::Intrinsic => intrinsic_size.expect("no intrinsic size").y,
+        // Flex children fill their cell, and thus do not contribute to the size of the track
+        let extent = |id: &Id| match self.children[id].config.size_hint[axis] {
+            SizeHint::Flex(_) => 0.0,
+            _ => self.children[id].size[axis],
         };
 
+        let mut sizes = (0..n)
+            .map(|i| match def(i) {
+                Track::Fixed(s) => s,
+                _ => 0.0,
+            })
+            .collect::<Vec<_>>();
+        // Children that span one track
+        for (id, cell) in cells.iter() {
+            let i = cell.start(axis);
+            if cell.span_along(axis) == 1 && fits_content(i) {
+                sizes[i] = sizes[i].max(extent(id));
+            }
+        }
+        // Children that span several tracks grow the tracks that fit content, if needed
+        for (id, cell) in cells.iter() {
+            let range = cell.start(axis)..cell.start(axis) + cell.span_along(axis);
+            if range.len() > 1 {
+                let current =
+                    sizes[range.clone()].iter().sum::<f32>() + gap * (range.len() - 1) as f32;
+                let growable = range
+                    .clone()
+                    .filter(|i| fits_content(*i))
+                    .collect::<Vec<_>>();
+                let missing = extent(id) - current;
+                if missing > 0.0 && !growable.is_empty() {
+                    for i in growable.iter() {
+                        sizes[*i] += missing / growable.len() as f32;
+                    }
+                }
+            }
+        }
+        // Fraction tracks share the space that is left
+        if let Some(available) = available {
+            let fraction = |i: usize| match def(i) {
+                Track::Fraction(f) => f,
+                _ => 0.0,
+            };
+            let total_fraction = (0..n).map(fraction).sum::<f32>();
+            if total_fraction > 0.0 {
+                let used = (0..n)
+                    .filter(|i| fraction(*i) == 0.0)
+                    .map(|i| sizes[i])
+                    .sum::<f32>();
+                let free = (available - used - gap * n.saturating_sub(1) as f32).max(0.0);
+                for i in 0..n {
+                    if let Track::Fraction(f) = def(i) {
+                        sizes[i] = free * f / total_fraction;
+                    }
+                }
+            }
+        }
+        sizes
+    }
+
+    /// Sets `size` and emits an event if it changed.
+    fn update_size(&mut self, new_size: Vec2) {
         if new_size != self.size {
             self.size = new_size;
             self.gui
                 .borrow_mut()
                 .push_event(Event::change(self.id, Widget::size));
         }
+    }
 
-        //
-        // Update positions of all children
-        //
-        let size = self.size;
-        // Keeps track of position along main axis
-        // TODO: needs special inital value sometimes
-        let mut main_progress = self.config.padding.min[main_axis];
-
-        for child in self.children.values_mut() {
-            let mut child_relative_pos = Vec2::zero();
-            if let Some(place) = child.config.place {
-                // Child does not participate in layout
-                child_relative_pos.x = match place.x {
-                    PlacementAxis::Fixed(x) => match place.x_anchor {
-                        Anchor::Min => x,
-                        Anchor::Center => (size.x - child.size.x) / 2.0 + x,
-                        Anchor::Max => size.x - child.size.x - x,
-                    },
-                };
-                child_relative_pos.y = match place.y {
-                    PlacementAxis::Fixed(y) => match place.y_anchor {
-                        Anchor::Min => y,
-                        Anchor::Center => (size.y - child.size.y) / 2.0 + y,
-                        Anchor::Max => size.y - child.size.y - y,
-                    },
-                };
-            } else {
-                // Layout algorithm
-                child_relative_pos[main_axis] = main_progress;
-                child_relative_pos[cross_axis] = match layout_cross_align {
-                    Anchor::Min => padding_min[cross_axis],
-                    Anchor::Center => (size[cross_axis] - child.size[cross_axis]) / 2.0,
-                    Anchor::Max => unimplemented!(),
-                };
-                main_progress += child.size[main_axis] + layout_main_margin;
-            };
-
-            // println!("Positioning Child [{}] relative_pos={:?}", child.id, child_relative_pos);
-            child.rel_pos = child_relative_pos;
+    /// Assigns the size that the parent has given to axes with `SizeHint::Flex`, and lays out
+    /// `self` again if it changed.
+    fn set_flex_size(&mut self, flex_size: Vec2<Option<f32>>) {
+        if flex_size != self.flex_size {
+            self.flex_size = flex_size;
+            self.layout_alg();
         }
     }
 }
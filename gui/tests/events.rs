@@ -1,4 +1,5 @@
-use gui::{lens::*, test_common::*, *};
+use gui::{lens::*, test_common::*, vec::*, *};
+use winit::event::MouseButton;
 // use slog::{o, Discard, Logger};
 
 /// (utility function) Test whether the layout alg completes in one single update.
@@ -68,7 +69,9 @@ fn test_select() {
     assert!(events.iter().any(|e| *e
         == Event {
             id: main_button_id,
-            kind: EventKind::Press
+            kind: EventKind::Press {
+                button: MouseButton::Left
+            }
         }));
 
     let mut has_made_new_widgets = false;
@@ -100,7 +103,7 @@ fn test_select() {
     assert_eq!(
         events
             .iter()
-            .filter(|e| e.kind != EventKind::Release)
+            .filter(|e| !matches!(e.kind, EventKind::Release { .. } | EventKind::Click { .. }))
             .count(),
         0
     );
@@ -125,14 +128,22 @@ fn test_button_click_capture_and_events() {
         .collect::<Vec<_>>();
     assert!(press_capture.mouse);
     assert!(release_capture.mouse);
-    assert_eq!(relevant_events.len(), 4);
+    assert_eq!(relevant_events.len(), 6);
     assert_events!(
         relevant_events,
         vec![
             EventKind::Hover,
-            EventKind::Press,
+            EventKind::Press {
+                button: MouseButton::Left
+            },
+            EventKind::Focus,
             EventKind::change(ToggleButton::state),
-            EventKind::Release,
+            EventKind::Release {
+                button: MouseButton::Left
+            },
+            EventKind::Click {
+                button: MouseButton::Left
+            },
         ]
     );
 }
@@ -249,3 +260,432 @@ pub fn print_widget_tree<D: GuiDrawer>(gui: &Gui<D>) {
 
     print_tree(&tree).unwrap();
 }
+
+#[test]
+fn test_focus() {
+    use winit::event::{ModifiersState, VirtualKeyCode};
+    let mut fix = TestFixture::fixture();
+    fix.update();
+    let button = fix.gui.get("Button 0").get_id();
+    let toggle = fix.gui.get("ToggleButton 0").get_id();
+
+    // Clicking a focusable widget focuses it
+    let ((events, _), _) = fix.click_widget("Button 0");
+    assert!(events.contains(&Event::new(button, EventKind::Focus)));
+    assert_eq!(fix.gui.internal.borrow().focused(), Some(button));
+
+    // Keys go to the focused widget only
+    let (events, capture) = fix.gui.press_key(VirtualKeyCode::A);
+    let key_events = events
+        .iter()
+        .filter(|event| match event.kind {
+            EventKind::KeyPress { .. } => true,
+            _ => false,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        key_events,
+        vec![&Event::new(
+            button,
+            EventKind::KeyPress {
+                key: VirtualKeyCode::A,
+                modifiers: ModifiersState::empty()
+            }
+        )]
+    );
+    assert!(!capture.keyboard);
+    fix.gui.release_key(VirtualKeyCode::A);
+
+    // Modifiers are reported with the key
+    fix.gui.press_key(VirtualKeyCode::LShift);
+    let (events, _) = fix.gui.press_key(VirtualKeyCode::Return);
+    assert!(events.contains(&Event::new(
+        button,
+        EventKind::KeyPress {
+            key: VirtualKeyCode::Return,
+            modifiers: ModifiersState::SHIFT
+        }
+    )));
+    fix.gui.release_key(VirtualKeyCode::LShift);
+
+    // Function, numpad, punctuation and modifier keys are sent as well
+    let (events, _) = fix.gui.press_key(VirtualKeyCode::LControl);
+    assert!(events.contains(&Event::new(
+        button,
+        EventKind::KeyPress {
+            key: VirtualKeyCode::LControl,
+            modifiers: ModifiersState::CTRL
+        }
+    )));
+    let (events, _) = fix.gui.press_key(VirtualKeyCode::Slash);
+    assert!(events.contains(&Event::new(
+        button,
+        EventKind::KeyPress {
+            key: VirtualKeyCode::Slash,
+            modifiers: ModifiersState::CTRL
+        }
+    )));
+    fix.gui.release_key(VirtualKeyCode::Slash);
+    let (events, _) = fix.gui.release_key(VirtualKeyCode::LControl);
+    assert!(events.contains(&Event::new(
+        button,
+        EventKind::KeyRelease {
+            key: VirtualKeyCode::LControl,
+            modifiers: ModifiersState::empty()
+        }
+    )));
+    for &key in &[VirtualKeyCode::F2, VirtualKeyCode::Numpad5] {
+        let (events, _) = fix.gui.press_key(key);
+        assert!(events.contains(&Event::new(
+            button,
+            EventKind::KeyPress {
+                key,
+                modifiers: ModifiersState::empty()
+            }
+        )));
+        fix.gui.release_key(key);
+    }
+
+    // Clicking another focusable widget moves focus
+    let ((events, _), _) = fix.click_widget("ToggleButton 0");
+    assert!(events.contains(&Event::new(button, EventKind::Unfocus)));
+    assert!(events.contains(&Event::new(toggle, EventKind::Focus)));
+
+    // Clicking outside removes focus
+    let (events, _) = fix.gui.press(Vec2::new(1000.0, 1000.0));
+    fix.gui.release();
+    assert!(events.contains(&Event::new(toggle, EventKind::Unfocus)));
+    assert_eq!(fix.gui.internal.borrow().focused(), None);
+
+    // Programmatic focus
+    assert!(fix.gui.focus("Button 1"));
+    let (events, _) = fix.update();
+    assert!(events.contains(&Event::new(
+        fix.gui.get("Button 1").get_id(),
+        EventKind::Focus
+    )));
+
+    // A misspelled alias or a removed widget leaves the focus unchanged
+    let focused = fix.gui.internal.borrow().focused();
+    assert!(!fix.gui.focus("Buton 1"));
+    assert!(!fix.gui.focus(12345));
+    let (events, _) = fix.update();
+    assert!(!events.iter().any(|e| e.kind == EventKind::Unfocus));
+    assert_eq!(fix.gui.internal.borrow().focused(), focused);
+}
+
+#[test]
+fn test_tab_traversal() {
+    use winit::event::VirtualKeyCode;
+    let mut fix = TestFixture::fixture();
+    fix.update();
+    let id = |fix: &TestFixture, alias: &str| fix.gui.get(alias).get_id();
+    let focused = |fix: &TestFixture| fix.gui.internal.borrow().focused();
+
+    // Tab starts at the first focusable widget, in tree order
+    let (events, _) = fix.gui.press_key(VirtualKeyCode::Tab);
+    assert!(events.contains(&Event::new(id(&fix, "Button 0"), EventKind::Focus)));
+    fix.gui.release_key(VirtualKeyCode::Tab);
+    fix.gui.press_key(VirtualKeyCode::Tab);
+    assert_eq!(focused(&fix), Some(id(&fix, "Button 1")));
+
+    // Shift-Tab goes back and wraps around
+    fix.gui.press_key(VirtualKeyCode::LShift);
+    fix.gui.press_key(VirtualKeyCode::Tab);
+    assert_eq!(focused(&fix), Some(id(&fix, "Button 0")));
+    fix.gui.press_key(VirtualKeyCode::Tab);
+    assert_eq!(focused(&fix), Some(id(&fix, "ToggleButton 4")));
+    fix.gui.release_key(VirtualKeyCode::LShift);
+
+    // Tab index overrides tree order; removed widgets are skipped
+    fix.gui.access("ToggleButton 2").configure(|config| {
+        config.set_tab_index(Some(0));
+    });
+    let removed = id(&fix, "Button 0");
+    fix.gui.internal.borrow_mut().remove(removed);
+    let order = fix.gui.tab_order();
+    assert_eq!(order[0], id(&fix, "ToggleButton 2"));
+    assert_eq!(order[1], id(&fix, "Button 1"));
+    assert!(!order.contains(&removed));
+    fix.gui.press_key(VirtualKeyCode::Tab);
+    assert_eq!(focused(&fix), Some(id(&fix, "ToggleButton 2")));
+}
+
+#[test]
+fn test_pointer_capture() {
+    let mut gui = TestGui::new();
+    let slider = gui.insert_in_root(Slider::new(0.0, 100.0));
+    let button = gui.insert_in_root(Button::new());
+    gui.update();
+    gui.update();
+    let center = |gui: &TestGui, id: Id| gui.get(id).pos + gui.get(id).size / 2.0;
+    let (slider_pos, button_pos) = (center(&gui, slider), center(&gui, button));
+
+    // The slider captures the pointer when pressed
+    gui.press(slider_pos);
+    assert_eq!(gui.internal.borrow().pointer_capture(), Some(slider));
+
+    // Moving over the button drags the slider without hovering the button
+    let (events, capture) = gui.move_mouse(button_pos);
+    assert!(capture.mouse);
+    assert!(events.contains(&Event::new(
+        slider,
+        EventKind::Drag {
+            delta: button_pos - slider_pos
+        }
+    )));
+    assert!(!events.contains(&Event::new(button, EventKind::Hover)));
+
+    // Releasing ends the capture, even outside the slider
+    let (events, _) = gui.release();
+    assert!(events.contains(&Event::new(
+        slider,
+        EventKind::Release {
+            button: MouseButton::Left
+        }
+    )));
+    assert_eq!(gui.internal.borrow().pointer_capture(), None);
+    let (events, _) = gui.move_mouse(button_pos + Vec2::new(1.0, 0.0));
+    assert!(events.contains(&Event::new(button, EventKind::Hover)));
+    assert!(!events.iter().any(|e| e.id == slider));
+}
+
+#[test]
+fn test_mouse_buttons_and_double_click() {
+    use std::time::Duration;
+    let mut gui = TestGui::new();
+    let button = gui.insert_in_root(Button::new());
+    let other = gui.insert_in_root(Button::new());
+    gui.update();
+    gui.update();
+    let center = |gui: &TestGui, id: Id| gui.get(id).pos + gui.get(id).size / 2.0;
+    let pos = center(&gui, button);
+    let click = |gui: &mut TestGui, pos: Vec2, button: MouseButton| {
+        let mut events = gui.press_button(pos, button).0;
+        events.extend(gui.release_button(button).0);
+        events
+    };
+
+    // Press and release carry the button; releasing on the widget makes a click
+    let events = click(&mut gui, pos, MouseButton::Right);
+    assert!(events.contains(&Event::new(
+        button,
+        EventKind::Press {
+            button: MouseButton::Right
+        }
+    )));
+    assert!(events.contains(&Event::new(
+        button,
+        EventKind::Click {
+            button: MouseButton::Right
+        }
+    )));
+
+    // A second click soon after, close to the first, is a double-click
+    let left = MouseButton::Left;
+    let double_click = Event::new(button, EventKind::DoubleClick { button: left });
+    let events = click(&mut gui, pos, left);
+    assert!(!events.contains(&double_click));
+    let events = click(&mut gui, pos + Vec2::new(2.0, 0.0), left);
+    assert!(events.contains(&double_click));
+    // ... but not a third one
+    let events = click(&mut gui, pos, left);
+    assert!(!events.contains(&double_click));
+
+    // Clicks too long apart are not double-clicks
+    gui.advance_time(Duration::from_secs(1));
+    let events = click(&mut gui, pos, left);
+    assert!(!events.contains(&double_click));
+    gui.advance_time(Duration::from_millis(499));
+    let events = click(&mut gui, pos, left);
+    assert!(events.contains(&double_click));
+    click(&mut gui, pos, left);
+    gui.advance_time(Duration::from_millis(500));
+    let events = click(&mut gui, pos, left);
+    assert!(!events.contains(&double_click));
+
+    // Clicks too far apart are not double-clicks
+    gui.internal
+        .borrow_mut()
+        .set_double_click_threshold(Duration::from_secs(1), 1.0);
+    let events = click(&mut gui, pos + Vec2::new(2.0, 0.0), left);
+    assert!(!events.contains(&double_click));
+
+    // Releasing outside the widget is not a click
+    gui.press(pos);
+    let events = gui.move_mouse(center(&gui, other)).0;
+    assert!(events.contains(&Event::new(other, EventKind::Hover)));
+    let events = gui.release().0;
+    assert!(events.contains(&Event::new(button, EventKind::Release { button: left })));
+    assert!(!events.contains(&Event::new(button, EventKind::Click { button: left })));
+    assert!(!events.iter().any(|e| e.id == other));
+}
+
+/// Remembers the events that reach it
+#[derive(Debug, Default)]
+struct Recorder {
+    local_events: Vec<Event>,
+    all_events: Vec<Event>,
+}
+impl Interactive for Recorder {
+    fn update(&mut self, _id: Id, local_events: Vec<Event>, ctx: &mut WidgetContext) {
+        self.local_events.extend(local_events);
+        self.all_events.extend(ctx.all_events());
+    }
+}
+
+#[test]
+fn test_consumed_events() {
+    let mut gui = TestGui::new();
+    let recorder = gui.insert_in_root(Recorder::default());
+    let select = gui
+        .get_mut(recorder)
+        .insert_child(Select::new().with_option("one".to_string(), "one".to_string()));
+    gui.update();
+    gui.update();
+    let center = |gui: &TestGui, id: Id| gui.get(id).pos + gui.get(id).size / 2.0;
+    let press = |id: Id| {
+        Event::new(
+            id,
+            EventKind::Press {
+                button: MouseButton::Left,
+            },
+        )
+    };
+
+    let recorded = |gui: &mut TestGui| {
+        let recorder = gui.get_mut(recorder).downcast_mut::<Recorder>().unwrap();
+        let local_events = std::mem::replace(&mut recorder.local_events, Vec::new());
+        let all_events = std::mem::replace(&mut recorder.all_events, Vec::new());
+        (local_events, all_events)
+    };
+
+    // Open the dropdown list. `Select` consumes the state change of its main button, so it does
+    // not reach `Recorder`...
+    let main_button = gui
+        .get(select)
+        .downcast_ref::<Select>()
+        .unwrap()
+        .main_button_id();
+    let toggle = Event::change(main_button, ToggleButton::state);
+    recorded(&mut gui);
+    let pos = center(&gui, main_button);
+    gui.press(pos);
+    assert_eq!(gui.consumed_events(), &[toggle.clone()][..]);
+    let (local_events, all_events) = recorded(&mut gui);
+    assert!(local_events.contains(&press(main_button)));
+    assert!(!local_events.contains(&toggle));
+    // ... but it is still in the view of all events
+    assert!(all_events.contains(&toggle));
+    gui.release();
+    assert!(gui.consumed_events().is_empty());
+
+    // Choose an option. `Select` consumes the press.
+    let opt = gui
+        .get(select)
+        .downcast_ref::<Select>()
+        .unwrap()
+        .get_widget_for_option("one")
+        .unwrap();
+    recorded(&mut gui);
+    let pos = center(&gui, opt);
+    gui.press(pos);
+    assert_eq!(gui.consumed_events(), &[press(opt)][..]);
+    let (local_events, all_events) = recorded(&mut gui);
+    assert!(!local_events.contains(&press(opt)));
+    assert!(all_events.contains(&press(opt)));
+}
+
+/// Measures every character as `char_width` wide
+#[derive(Clone, Debug)]
+struct CharWidth(f32);
+impl TextCalculator for CharWidth {
+    fn text_size(&mut self, text: &str) -> Vec2 {
+        Vec2::new(self.0 * text.chars().count() as f32, 10.0)
+    }
+    fn char_offsets(&mut self, text: &str) -> Vec<f32> {
+        (0..=text.chars().count())
+            .map(|i| self.0 * i as f32)
+            .collect()
+    }
+}
+/// Returns `ops` from the next update
+struct OpDrawer {
+    ops: Vec<WidgetOp>,
+    char_width: f32,
+    /// Layers that text calculators were requested for
+    layers: Vec<u32>,
+}
+impl GuiDrawer for OpDrawer {
+    type Context = ();
+    type Calculator = CharWidth;
+    fn window_size(&self, _ctx: &mut ()) -> Vec2 {
+        Vec2::new(400.0, 300.0)
+    }
+    fn transform_mouse(&self, m: Vec2, _ctx: &mut ()) -> Vec2 {
+        m
+    }
+    fn update(
+        &mut self,
+        _gui: &Gui<Self>,
+        _events: &[Event],
+        _log: slog::Logger,
+        _ctx: &mut (),
+    ) -> Vec<WidgetOp> {
+        std::mem::replace(&mut self.ops, Vec::new())
+    }
+    fn text_calc(&mut self, layer: u32, _ctx: &mut ()) -> CharWidth {
+        self.layers.push(layer);
+        CharWidth(self.char_width)
+    }
+}
+
+#[test]
+fn test_widget_ops() {
+    let drawer = OpDrawer {
+        ops: Vec::new(),
+        char_width: 10.0,
+        layers: Vec::new(),
+    };
+    let mut gui = TestGui::with_drawer(drawer, ());
+    let text = gui.insert_in_root(TextField::new("ab".to_string()));
+    // (placed widgets are on a layer above their parent)
+    gui.access(text).configure(|config| {
+        config.set_placement(Placement::fixed(0.0, 50.0));
+    });
+    let image = gui.insert_in_root(Image::new("icon.png".to_string()));
+    let other_text = gui.insert_in_root(TextField::new("ab".to_string()));
+    gui.update();
+    gui.update();
+    assert_eq!(gui.get(text).size, Vec2::new(20.0, 10.0));
+    assert_eq!(gui.get(image).size, Vec2::zero());
+    assert!(!gui.redraw_requested());
+
+    // A font and an image finished loading
+    gui.drawer_mut().char_width = 20.0;
+    gui.drawer_mut().ops = vec![
+        WidgetOp::Remeasure(text),
+        WidgetOp::SetIntrinsicSize(image, Vec2::new(8.0, 6.0)),
+        WidgetOp::RequestRedraw,
+    ];
+    let (events, _) = gui.update();
+    // The operations are applied before `update` returns
+    assert_eq!(gui.get(text).size, Vec2::new(40.0, 10.0));
+    assert_eq!(gui.get(image).size, Vec2::new(8.0, 6.0));
+    assert!(gui.redraw_requested());
+    assert_eq!(gui.get(text).layer, 1);
+    assert_eq!(gui.drawer().layers.last(), Some(&1));
+    // (only the text calculator of layer 1 was taken again)
+    assert_eq!(gui.get(other_text).layer, 0);
+    assert_eq!(gui.get(other_text).size, Vec2::new(20.0, 10.0));
+    assert!(events.contains(&Event::change(text, Widget::size)));
+    assert!(events.contains(&Event::change(image, Image::intrinsic_size)));
+    assert!(events.contains(&Event::change(image, Widget::size)));
+
+    // Nothing changes in the next update
+    let (events, _) = gui.update();
+    assert!(!gui.redraw_requested());
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event.kind, EventKind::Change { .. })));
+}
@@ -1,4 +1,14 @@
-use gui::{lens::*, test_common::*, vec::*, *};
+use gui::{default::Container, lens::*, test_common::*, vec::*, *};
+
+/// Asserts that the layout is complete: another update changes nothing
+fn assert_layout_settled(gui: &mut TestGui) {
+    let (events, _) = gui.update();
+    let changes = events
+        .into_iter()
+        .filter(|event| matches!(event.kind, EventKind::Change { .. }))
+        .collect::<Vec<_>>();
+    assert!(changes.is_empty(), "Changes after layout: {:?}", changes);
+}
 
 #[test]
 fn test_select_on_click() {
@@ -45,3 +55,475 @@ fn test_setting_button_size() {
         SIZE
     );
 }
+
+#[test]
+fn test_layout_wrap() {
+    let mut gui = TestGui::new();
+    let id = gui.insert_in_root(Container::new());
+    gui.access(id).configure(|config| {
+        config
+            .set_layout(Axis::X, true, Anchor::Min, 2.0)
+            .set_layout_cross_margin(3.0)
+            .set_size_hint(SizeHint::External(50.0), SizeHint::Minimize)
+            .set_padding(1.0, 1.0, 1.0, 1.0);
+    });
+    let children = (0..5)
+        .map(|_| {
+            let child = gui.get_mut(id).insert_child(Container::new());
+            gui.access(id)
+                .chain(Widget::child(child))
+                .configure(|config| {
+                    config.set_size(20.0, 10.0);
+                });
+            child
+        })
+        .collect::<Vec<_>>();
+    gui.update();
+
+    // Two children fit on each line: 1 + 20 + 2 + 20 <= 49
+    let positions = children
+        .iter()
+        .map(|child| gui.get(*child).pos)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        positions,
+        vec![
+            Vec2::new(1.0, 1.0),
+            Vec2::new(23.0, 1.0),
+            Vec2::new(1.0, 14.0),
+            Vec2::new(23.0, 14.0),
+            Vec2::new(1.0, 27.0),
+        ]
+    );
+    assert_eq!(
+        *gui.access(id).chain(Widget::size).get(),
+        Vec2::new(50.0, 38.0)
+    );
+}
+
+/// Creates a container of size 100x20 with three 10x10 children
+fn toolbar(gui: &mut TestGui, configure: impl FnOnce(&mut WidgetConfig)) -> (Id, Vec<Id>) {
+    let id = gui.insert_in_root(Container::new());
+    gui.access(id).configure(|config| {
+        config.set_size(100.0, 20.0);
+        configure(config);
+    });
+    let children = (0..3)
+        .map(|_| {
+            let child = gui.get_mut(id).insert_child(Container::new());
+            gui.access(id)
+                .chain(Widget::child(child))
+                .configure(|config| {
+                    config.set_size(10.0, 10.0);
+                });
+            child
+        })
+        .collect::<Vec<_>>();
+    (id, children)
+}
+
+#[test]
+fn test_layout_align_max() {
+    let mut gui = TestGui::new();
+    let (_, children) = toolbar(&mut gui, |config| {
+        config
+            .set_layout_main_align(Anchor::Max)
+            .set_layout_cross_align(Anchor::Max)
+            .layout_main_margin = 5.0;
+    });
+    gui.update();
+    let positions = children
+        .iter()
+        .map(|child| gui.get(*child).pos)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        positions,
+        vec![
+            Vec2::new(60.0, 10.0),
+            Vec2::new(75.0, 10.0),
+            Vec2::new(90.0, 10.0)
+        ]
+    );
+}
+
+#[test]
+fn test_layout_justify() {
+    for (justify, expected_x) in vec![
+        (Justify::SpaceBetween, [0.0, 45.0, 90.0]),
+        (
+            Justify::SpaceAround,
+            [35.0 / 3.0, 50.0 - 5.0, 100.0 - 10.0 - 35.0 / 3.0],
+        ),
+        (Justify::SpaceEvenly, [17.5, 45.0, 72.5]),
+    ] {
+        let mut gui = TestGui::new();
+        let (_, children) = toolbar(&mut gui, |config| {
+            config.set_layout_justify(justify);
+        });
+        gui.update();
+        for (child, x) in children.iter().zip(expected_x.iter()) {
+            let pos = gui.get(*child).pos;
+            assert!(
+                (pos.x - x).abs() < 0.001,
+                "{:?}: expected x={} but got {}",
+                justify,
+                x,
+                pos.x
+            );
+        }
+    }
+}
+
+#[test]
+fn test_flex() {
+    let mut gui = TestGui::new();
+    let id = gui.insert_in_root(Container::new());
+    gui.access(id).configure(|config| {
+        config
+            .set_size(100.0, 30.0)
+            .set_layout_cross_align(Anchor::Center);
+    });
+    let hints = vec![
+        (SizeHint::External(20.0), SizeHint::External(10.0)),
+        (SizeHint::Flex(1.0), SizeHint::Flex(1.0)),
+        (SizeHint::Flex(3.0), SizeHint::Minimize),
+    ];
+    let children = hints
+        .into_iter()
+        .map(|(x, y)| {
+            let child = gui.get_mut(id).insert_child(Container::new());
+            gui.access(id)
+                .chain(Widget::child(child))
+                .configure(|config| {
+                    config
+                        .set_size_hint(x, y)
+                        .set_layout_main_align(Anchor::Center);
+                });
+            child
+        })
+        .collect::<Vec<_>>();
+    // A grandchild, to check that flex children lay out their own children with the final size
+    let grandchild = gui.get_mut(children[2]).insert_child(Container::new());
+    gui.get_mut(grandchild).config.set_size(10.0, 10.0);
+
+    gui.update();
+
+    let sizes = children
+        .iter()
+        .map(|child| gui.get(*child).size)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sizes,
+        vec![
+            Vec2::new(20.0, 10.0),
+            Vec2::new(20.0, 30.0),
+            Vec2::new(60.0, 10.0)
+        ]
+    );
+    assert_eq!(gui.get(children[2]).pos, Vec2::new(40.0, 10.0));
+    assert_eq!(gui.get(grandchild).pos, Vec2::new(65.0, 10.0));
+
+    assert_layout_settled(&mut gui);
+}
+
+#[test]
+fn test_placement_percentage_and_relative() {
+    let mut gui = TestGui::new();
+    let parent = gui.insert_in_root(Container::new());
+    gui.access(parent).configure(|config| {
+        config
+            .set_size(200.0, 100.0)
+            .set_placement(Placement::fixed(10.0, 10.0));
+    });
+    let hud = gui.get_mut(parent).insert_child(Container::new());
+    gui.access(hud).configure(|config| {
+        config
+            .set_size(20.0, 10.0)
+            .set_placement(Placement::percentage(20.0, 50.0).x_anchor(Anchor::Max));
+    });
+    // Placed below `hud`, in a different part of the tree
+    let popup = gui.insert_in_root(Container::new());
+    gui.access(popup).configure(|config| {
+        config
+            .set_size(30.0, 30.0)
+            .set_placement(Placement::relative(hud, 0.0, 2.0).y_anchor(Anchor::Max));
+    });
+
+    gui.update();
+
+    // 20% from the right edge: 10 + 200 - 20 - 40
+    assert_eq!(gui.get(hud).pos, Vec2::new(150.0, 60.0));
+    assert_eq!(gui.get(popup).pos, Vec2::new(150.0, 72.0));
+    // Top-level widgets have no parent
+    assert_eq!(gui.try_parent(popup), None);
+    assert_eq!(gui.try_parent(hud), Some(parent));
+    assert_layout_settled(&mut gui);
+}
+
+#[test]
+fn test_min_max_size() {
+    let mut gui = TestGui::new();
+    // An empty container that should not collapse
+    let empty = gui.insert_in_root(Container::new());
+    gui.access(empty).configure(|config| {
+        config.set_min_size(Some(50.0), Some(20.0));
+    });
+    // Flex children, one of which has a max width
+    let row = gui.insert_in_root(Container::new());
+    gui.access(row).configure(|config| {
+        config.set_size(100.0, 10.0);
+    });
+    let children = (0..2)
+        .map(|_| gui.get_mut(row).insert_child(Container::new()))
+        .collect::<Vec<_>>();
+    for child in children.iter() {
+        gui.access(*child).configure(|config| {
+            config.set_size_hint(SizeHint::Flex(1.0), SizeHint::Minimize);
+        });
+    }
+    gui.access(children[0]).configure(|config| {
+        config.set_max_size(Some(20.0), None);
+    });
+
+    let (events, _) = gui.update();
+    assert_eq!(gui.get(empty).size, Vec2::new(50.0, 20.0));
+    assert_eq!(gui.get(children[0]).size.x, 20.0);
+    assert_eq!(gui.get(children[1]).size.x, 80.0);
+
+    // Changing the constraints emits the usual change event
+    gui.access(empty).configure(|config| {
+        config.set_max_size(Some(30.0), None);
+    });
+    let (events_after, _) = gui.update();
+    assert_eq!(gui.get(empty).size, Vec2::new(50.0, 20.0));
+    gui.access(empty).configure(|config| {
+        config.set_min_size(None, None);
+    });
+    let (events_after_2, _) = gui.update();
+    assert_eq!(gui.get(empty).size, Vec2::new(0.0, 0.0));
+    assert!(events.contains(&Event::change(empty, Widget::size)));
+    assert!(!events_after.contains(&Event::change(empty, Widget::size)));
+    assert!(events_after_2.contains(&Event::change(empty, Widget::size)));
+}
+
+#[test]
+fn test_grid() {
+    let mut gui = TestGui::new();
+    let id = gui.insert_in_root(Container::new());
+    gui.access(id).configure(|config| {
+        config
+            .set_size_hint(SizeHint::External(200.0), SizeHint::Minimize)
+            .set_grid(
+                Grid::new(
+                    &[Track::Fixed(30.0), Track::Auto, Track::Fraction(1.0)],
+                    &[],
+                )
+                .unwrap()
+                .gap(5.0, 2.0),
+            );
+    });
+    let sizes = vec![
+        Vec2::new(10.0, 10.0),
+        Vec2::new(50.0, 10.0),
+        Vec2::new(10.0, 10.0),
+        Vec2::new(10.0, 20.0),
+        Vec2::new(40.0, 10.0),
+    ];
+    let children = sizes
+        .iter()
+        .map(|size| {
+            let child = gui.get_mut(id).insert_child(Container::new());
+            gui.access(child).configure(|config| {
+                config.set_size(size.x, size.y);
+            });
+            child
+        })
+        .collect::<Vec<_>>();
+    // The last child spans the first two columns of the third row
+    gui.access(children[4]).configure(|config| {
+        config.set_grid_cell(GridCell::new(0, 2).span(2, 1));
+    });
+    gui.update();
+
+    // Columns: 30, 50 (auto), 200 - 30 - 50 - 2 * 5 = 110 (fraction)
+    // Rows: 10, 20, 10
+    let positions = children
+        .iter()
+        .map(|child| gui.get(*child).pos)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        positions,
+        vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(35.0, 0.0),
+            Vec2::new(90.0, 0.0),
+            Vec2::new(0.0, 12.0),
+            Vec2::new(0.0, 34.0),
+        ]
+    );
+    assert_eq!(gui.get(id).size, Vec2::new(200.0, 44.0));
+
+    // More tracks than can be defined
+    let columns = vec![Track::Auto; MAX_GRID_TRACKS + 1];
+    assert_eq!(Grid::new(&columns, &[]), None);
+    assert!(Grid::new(&columns[1..], &columns[1..]).is_some());
+}
+
+#[test]
+fn test_grid_zero_span() {
+    let mut gui = TestGui::new();
+    let id = gui.insert_in_root(Container::new());
+    gui.access(id).configure(|config| {
+        config.set_grid(
+            Grid::new(&[Track::Fixed(30.0), Track::Fixed(20.0)], &[])
+                .unwrap()
+                .gap(5.0, 0.0),
+        );
+    });
+    let child = gui.get_mut(id).insert_child(Container::new());
+    gui.access(child).configure(|config| {
+        config.set_size(10.0, 10.0).set_grid_cell(GridCell {
+            column: 1,
+            row: 0,
+            column_span: 0,
+            row_span: 0,
+        });
+    });
+    gui.update();
+
+    // Behaves like a span of one cell
+    assert_eq!(gui.get(child).pos, Vec2::new(35.0, 0.0));
+    assert_eq!(gui.get(id).size, Vec2::new(55.0, 10.0));
+}
+
+#[test]
+fn test_clip() {
+    let mut gui = TestGui::new();
+    // Two nested clipping containers that partly overlap, and one that does not clip
+    let outer = gui.insert_in_root(Container::new());
+    gui.access(outer).configure(|config| {
+        config.set_size(100.0, 50.0).set_clip_children(true);
+    });
+    let spacer = gui.get_mut(outer).insert_child(Container::new());
+    gui.access(spacer).configure(|config| {
+        config.set_size(80.0, 10.0);
+    });
+    let inner = gui.get_mut(outer).insert_child(Container::new());
+    gui.access(inner).configure(|config| {
+        config.set_size(40.0, 40.0).set_clip_children(true);
+    });
+    let leaf = gui.get_mut(inner).insert_child(Container::new());
+    gui.access(leaf).configure(|config| {
+        config.set_size(60.0, 60.0);
+    });
+    gui.update();
+
+    assert_eq!(gui.get(outer).clip, None);
+    assert_eq!(
+        gui.get(inner).clip,
+        Some(Rect::new(Vec2::zero(), Vec2::new(100.0, 50.0)))
+    );
+    assert_eq!(
+        gui.get(leaf).clip,
+        Some(Rect::new(Vec2::new(80.0, 0.0), Vec2::new(100.0, 40.0)))
+    );
+
+    // Turning clipping off propagates to all descendants
+    gui.access(outer).configure(|config| {
+        config.set_clip_children(false);
+    });
+    gui.update();
+    assert_eq!(gui.get(inner).clip, None);
+    assert_eq!(
+        gui.get(leaf).clip,
+        Some(Rect::new(Vec2::new(80.0, 0.0), Vec2::new(120.0, 40.0)))
+    );
+}
+
+#[test]
+fn test_text_wrap() {
+    let mut gui = TestGui::new();
+    let column = gui.insert_in_root(Container::new());
+    gui.access(column).configure(|config| {
+        config
+            .set_size_hint(SizeHint::External(100.0), SizeHint::Minimize)
+            .set_layout_direction(Axis::Y);
+    });
+    // Width given by the parent
+    let flex = gui
+        .get_mut(column)
+        .insert_child(TextField::new("aaa bbb ccc dddd eeeeeeeeeeee".to_string()).with_wrap(true));
+    gui.access(flex).configure(|config| {
+        config.set_size_hint(SizeHint::Flex(1.0), SizeHint::Intrinsic);
+    });
+    // Fixed width
+    let fixed = gui
+        .get_mut(column)
+        .insert_child(TextField::new("ab cd\nef".to_string()).with_wrap(true));
+    gui.access(fixed).configure(|config| {
+        config.set_size_hint(SizeHint::External(40.0), SizeHint::Intrinsic);
+    });
+    gui.update();
+    assert_layout_settled(&mut gui);
+
+    // "aaa bbb" / "ccc dddd" / "eeeeeeeeeeee" (too long, but it can not be broken)
+    assert_eq!(gui.get(flex).size, Vec2::new(100.0, 30.0));
+    // "ab" / "cd" / "ef"
+    assert_eq!(gui.get(fixed).size, Vec2::new(40.0, 30.0));
+    assert_eq!(gui.get(fixed).rel_pos.y, 30.0);
+    assert_eq!(gui.get(column).size, Vec2::new(100.0, 60.0));
+
+    let lines = NoDrawer
+        .text_calc(0, &mut ())
+        .wrap_lines("aaa bbb ccc dddd eeeeeeeeeeee", 100.0);
+    assert_eq!(
+        lines,
+        vec![
+            TextLine {
+                start: 0,
+                end: 7,
+                width: 70.0
+            },
+            TextLine {
+                start: 8,
+                end: 16,
+                width: 80.0
+            },
+            TextLine {
+                start: 17,
+                end: 29,
+                width: 120.0
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_text_wrap_in_grid() {
+    let mut gui = TestGui::new();
+    let grid = gui.insert_in_root(Container::new());
+    gui.access(grid).configure(|config| {
+        config
+            .set_size_hint(SizeHint::External(130.0), SizeHint::Minimize)
+            .set_grid(Grid::new(&[Track::Fixed(30.0), Track::Fraction(1.0)], &[]).unwrap());
+    });
+    let label = gui.get_mut(grid).insert_child(Container::new());
+    gui.get_mut(label).config.set_size(30.0, 10.0);
+    // Width given by the fraction column
+    let text = gui
+        .get_mut(grid)
+        .insert_child(TextField::new("aaa bbb ccc".to_string()).with_wrap(true));
+    gui.access(text).configure(|config| {
+        config.set_size_hint(SizeHint::Flex(1.0), SizeHint::Intrinsic);
+    });
+    let below = gui.get_mut(grid).insert_child(Container::new());
+    gui.get_mut(below).config.set_size(10.0, 10.0);
+
+    // The first update is enough to measure the text with the width of its column
+    gui.update();
+    // "aaa bbb" / "ccc"
+    assert_eq!(gui.get(text).size, Vec2::new(100.0, 20.0));
+    assert_eq!(gui.get(below).rel_pos, Vec2::new(0.0, 20.0));
+    assert_eq!(gui.get(grid).size, Vec2::new(130.0, 30.0));
+    assert_layout_settled(&mut gui);
+}